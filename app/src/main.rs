@@ -1,7 +1,14 @@
+use chrono::{Duration, Local, NaiveDate};
 use clap::{Parser, ValueEnum};
-use obsidian_logging::{add, edit, list, Config, ListType, TimeFormat};
+use obsidian_logging::config::{check_config_files, config_files, user_config_path};
+use obsidian_logging::utils::parse_date_expression;
+use obsidian_logging::{
+    add, amend, edit, init, list, properties, search, Config, Error, ListType, OutputFormat,
+    Result, TimeFormat, Warning,
+};
 use std::env;
-use std::io::{self, Read};
+use std::io::{self, IsTerminal, Read};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(
@@ -24,8 +31,27 @@ USAGE EXAMPLES:
   obsidian-logging -p call {0}       # Use phrase with placeholder {0}
   obsidian-logging -l                # List today's entries
   obsidian-logging -b 1              # List entries from 1 day ago
+  obsidian-logging --since 7d        # List entries from the last 7 days
+  obsidian-logging --from 2024-01-01 --to 2024-01-07 -c all  # List a date range
+  obsidian-logging -c all -o json    # Today's entries as JSON
+  obsidian-logging --since 7d -o csv # Last week's entries as CSV
+  obsidian-logging -g deploy         # Search all notes for \"deploy\"
+  obsidian-logging -g 'billing.*deploy' -r -i --limit 1  # Last matching deploy
+  obsidian-logging --at last fixed typo     # Replace the text of the last entry
+  obsidian-logging --at 14:30 -t 15:00     # Move the 14:30 entry to 15:00
+  obsidian-logging --at 2 --delete         # Delete the second entry
+  obsidian-logging --task call the bank    # Add an open task: - [ ] 14:30:00 call the bank
+  obsidian-logging --done last --stamp     # Tick off the last task with the completion time
+  obsidian-logging --tasks open -b 1       # List yesterday's open tasks
+  obsidian-logging --init            # Create the configuration file interactively
+  obsidian-logging --check-config    # Validate the configuration file
+  obsidian-logging -P work standup   # Log to the vault of the 'work' profile
+  obsidian-logging --property mood=good  # Set a property in the front matter
+  obsidian-logging --column end=17:00 Client workshop  # Fill a column from table_columns
   obsidian-logging -e                # Edit today's file
   obsidian-logging -b 1 -e           # Edit file from 1 day ago
+  obsidian-logging -b 1 forgot this  # Add entry to yesterday's file
+  obsidian-logging --date 2024-01-15 -t 21:00 entry  # Add entry to a specific date
   obsidian-logging -T table -l       # List in table format
   obsidian-logging -f 12 -t 2:30 PM  # Use 12-hour format with time
   echo \"My log entry\" | obsidian-logging -S        # Read from stdin
@@ -33,21 +59,37 @@ USAGE EXAMPLES:
 
 CONFIGURATION:
   Configuration file location:
-    Linux/macOS: ~/.config/obsidian-logging/obsidian-logging.yaml
+    Linux/macOS: $XDG_CONFIG_HOME/obsidian-logging/obsidian-logging.yaml
+                 (default ~/.config/obsidian-logging/obsidian-logging.yaml)
     Windows: %APPDATA%\\obsidian-logging\\obsidian-logging.yaml
+  A .obsidian-logging.yaml in the current directory or a parent directory
+  is layered on top. --config <path> or $OBSIDIAN_LOGGING_CONFIG selects a
+  single file instead.
 
-  Environment variable: $OBSIDIAN_VAULT_DIR (overrides vault setting in config)
+  Profiles defined under 'profiles:' override the top-level settings. Select
+  one with --profile <name>, $OBSIDIAN_LOGGING_PROFILE or 'default_profile:'.
+
+  Environment variable: $OBSIDIAN_VAULT_DIR (overrides vault setting in config,
+  unless the selected profile sets a vault)
 
 TEMPLATE VARIABLES:
   {today}      Current date (YYYY-MM-DD)
   {yesterday}  Yesterday's date
   {tomorrow}   Tomorrow's date
   {weekday}    Localized weekday name
-  {created}    Creation timestamp (YYYY-MM-DD HH:mm:ss)"
+  {created}    Creation timestamp (YYYY-MM-DD HH:mm:ss)
+  {week}       ISO week number; also {year}, {month}, {day} and {quarter}
+  {date+7d:%Y-%m-%d}  Date offset (d, w, m, y) and strftime format
+  {env:NAME}   Environment variable; names from template_variables work too
+  {if weekend}...{else}...{end}  Also workday, monday..sunday, not <cond>"
 )]
 struct Cli {
-    /// Override list type (bullet or table)
-    #[arg(short = 'T', value_enum, help = "Override list type: bullet or table")]
+    /// Override list type (bullet, table or custom)
+    #[arg(
+        short = 'T',
+        value_enum,
+        help = "Override list type: bullet, table or custom (the configured entry_format)"
+    )]
     list_type: Option<ListTypeArg>,
 
     /// Override time format (12 or 24)
@@ -62,14 +104,44 @@ struct Cli {
     )]
     time: Option<String>,
 
-    /// List entries from specified days ago
+    /// List, edit or add entries for the day specified days ago
     #[arg(
         short = 'b',
         default_value = "0",
-        help = "Days ago (0 = today, 1 = yesterday, etc.)"
+        allow_negative_numbers = true,
+        help = "Days ago (0 = today, 1 = yesterday, -1 = tomorrow, etc.)"
     )]
     days_ago: i64,
 
+    /// List, edit or add entries for a specific date
+    #[arg(
+        short = 'd',
+        long,
+        conflicts_with = "days_ago",
+        value_parser = parse_date_arg,
+        help = "Date to list, edit or add entries for (YYYY-MM-DD, yesterday, 3d, 1w, ...)"
+    )]
+    date: Option<NaiveDate>,
+
+    /// List entries starting from this date
+    #[arg(
+        long,
+        visible_alias = "since",
+        conflicts_with_all = ["days_ago", "date", "edit", "stdin", "entry"],
+        value_parser = parse_date_arg,
+        help = "List entries from this date (YYYY-MM-DD, yesterday, 7d, 2w, ...) up to --to or today"
+    )]
+    from: Option<NaiveDate>,
+
+    /// List entries up to this date
+    #[arg(
+        long,
+        requires = "from",
+        value_parser = parse_date_arg,
+        help = "Last date to list entries for when used with --from (defaults to today)"
+    )]
+    to: Option<NaiveDate>,
+
     /// Edit today's file or file from specified days ago
     #[arg(short, long, help = "Open file in $EDITOR (defaults to vim)")]
     edit: bool,
@@ -98,6 +170,17 @@ struct Cli {
     #[arg(short = 'H', long, help = "Include table header when listing entries")]
     header: bool,
 
+    /// Output format for listed entries
+    #[arg(
+        short = 'o',
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        conflicts_with_all = ["edit", "stdin", "search", "entry"],
+        help = "Output format when listing: markdown, json, jsonl, csv or tsv"
+    )]
+    output: Option<OutputFormatArg>,
+
     /// Category for the log entry (uses section_header_<category> from config)
     /// Can be specified multiple times to list multiple categories
     /// Use 'all' to list all categories
@@ -117,10 +200,161 @@ struct Cli {
     )]
     phrase: Option<String>,
 
+    /// Search all daily notes for entries matching a pattern
+    #[arg(
+        short = 'g',
+        long,
+        value_name = "PATTERN",
+        conflicts_with_all = ["edit", "stdin", "list", "from", "date", "days_ago", "entry"],
+        help = "Search all daily and periodic notes for entries containing PATTERN (newest first)"
+    )]
+    search: Option<String>,
+
+    /// Case-insensitive search
+    #[arg(
+        short = 'i',
+        long,
+        requires = "search",
+        help = "Ignore case when searching"
+    )]
+    ignore_case: bool,
+
+    /// Interpret the search pattern as a regular expression
+    #[arg(
+        short = 'r',
+        long,
+        requires = "search",
+        help = "Treat the search pattern as a regular expression"
+    )]
+    regex: bool,
+
+    /// Maximum number of search results
+    #[arg(
+        long,
+        requires = "search",
+        help = "Show at most this many search results"
+    )]
+    limit: Option<usize>,
+
+    /// Show the oldest search results first
+    #[arg(
+        long,
+        requires = "search",
+        help = "Show the oldest search results first instead of the newest"
+    )]
+    oldest_first: bool,
+
+    /// Select an existing entry to change or delete
+    #[arg(
+        long,
+        value_name = "ENTRY",
+        value_parser = parse_selector_arg,
+        conflicts_with_all = ["edit", "list", "search", "from", "output"],
+        help = "Change the entry at this time (14:30:00), index in the listing (2) or 'last'. New text comes from the arguments or -S, a new time from -t"
+    )]
+    at: Option<amend::EntrySelector>,
+
+    /// Delete the entry selected with --at
+    #[arg(
+        long,
+        requires = "at",
+        conflicts_with_all = ["entry", "stdin", "time"],
+        help = "Delete the entry selected with --at"
+    )]
+    delete: bool,
+
+    /// Add the entry as an open task
+    #[arg(
+        long,
+        conflicts_with_all = ["edit", "list", "search", "from", "at"],
+        help = "Add the entry as an open task: - [ ] 14:30:00 text"
+    )]
+    task: bool,
+
+    /// Tick off a task
+    #[arg(
+        long,
+        value_name = "ENTRY",
+        value_parser = parse_selector_arg,
+        conflicts_with_all = ["edit", "list", "search", "from", "at", "entry", "stdin", "time", "task"],
+        help = "Tick off the task at this time (14:30:00), index in the listing (2) or 'last'"
+    )]
+    done: Option<amend::EntrySelector>,
+
+    /// Append the completion time to a task ticked off with --done
+    #[arg(
+        long,
+        requires = "done",
+        help = "With --done, append when the task was completed (✅ 2026-10-16 15:02:11)"
+    )]
+    stamp: bool,
+
+    /// List only open or done tasks
+    #[arg(
+        long,
+        value_enum,
+        value_name = "STATUS",
+        conflicts_with_all = ["edit", "search", "at", "done", "entry", "stdin", "task"],
+        help = "List only tasks: open, done or all"
+    )]
+    tasks: Option<TaskFilterArg>,
+
+    /// Cells of the extra table columns of the entry
+    #[arg(
+        long,
+        value_name = "COLUMN=VALUE",
+        value_parser = parse_column_arg,
+        conflicts_with_all = ["edit", "list", "search", "from", "at", "done"],
+        help = "Fill a column from table_columns for the new entry, e.g. --column end=17:00. Can be repeated"
+    )]
+    column: Vec<(String, String)>,
+
+    /// Front matter properties to set
+    #[arg(
+        long,
+        value_name = "KEY=VALUE",
+        value_parser = parse_property_arg,
+        conflicts_with_all = ["edit", "list", "search", "from", "at"],
+        help = "Set a front matter property of the note, e.g. --property mood=good. Can be repeated and combined with an entry"
+    )]
+    property: Vec<(String, serde_yaml::Value)>,
+
     /// The log entry text to add
     #[arg(help = "Log entry text (if not provided, lists entries)")]
     entry: Vec<String>,
 
+    /// Configuration file to use instead of the user and project configuration
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Read the configuration from this file only (default: $OBSIDIAN_LOGGING_CONFIG, or the user config with a project-local .obsidian-logging.yaml on top)"
+    )]
+    config: Option<PathBuf>,
+
+    /// Named profile from the configuration to use
+    #[arg(
+        short = 'P',
+        long,
+        value_name = "NAME",
+        help = "Use this profile from the configuration (default: $OBSIDIAN_LOGGING_PROFILE or default_profile)"
+    )]
+    profile: Option<String>,
+
+    /// Create the configuration file interactively
+    #[arg(
+        long,
+        conflicts_with_all = ["check_config", "entry", "list", "edit", "search", "from", "at"],
+        help = "Create the configuration file by answering a few questions (written to --config or the user configuration)"
+    )]
+    init: bool,
+
+    /// Validate the configuration file
+    #[arg(
+        long,
+        help = "Check the configuration file for errors, unknown keys and missing paths"
+    )]
+    check_config: bool,
+
     /// Print version information
     #[arg(short = 'v', long, help = "Print version information")]
     version: bool,
@@ -130,6 +364,23 @@ struct Cli {
 enum ListTypeArg {
     Bullet,
     Table,
+    Custom,
+}
+
+#[derive(ValueEnum, Clone)]
+enum OutputFormatArg {
+    Markdown,
+    Json,
+    Jsonl,
+    Csv,
+    Tsv,
+}
+
+#[derive(ValueEnum, Clone)]
+enum TaskFilterArg {
+    Open,
+    Done,
+    All,
 }
 
 #[derive(ValueEnum, Clone)]
@@ -145,6 +396,29 @@ impl From<ListTypeArg> for ListType {
         match arg {
             ListTypeArg::Bullet => ListType::Bullet,
             ListTypeArg::Table => ListType::Table,
+            ListTypeArg::Custom => ListType::Custom,
+        }
+    }
+}
+
+impl From<OutputFormatArg> for OutputFormat {
+    fn from(arg: OutputFormatArg) -> Self {
+        match arg {
+            OutputFormatArg::Markdown => OutputFormat::Markdown,
+            OutputFormatArg::Json => OutputFormat::Json,
+            OutputFormatArg::Jsonl => OutputFormat::JsonLines,
+            OutputFormatArg::Csv => OutputFormat::Csv,
+            OutputFormatArg::Tsv => OutputFormat::Tsv,
+        }
+    }
+}
+
+impl From<TaskFilterArg> for list::TaskFilter {
+    fn from(arg: TaskFilterArg) -> Self {
+        match arg {
+            TaskFilterArg::Open => list::TaskFilter::Open,
+            TaskFilterArg::Done => list::TaskFilter::Done,
+            TaskFilterArg::All => list::TaskFilter::All,
         }
     }
 }
@@ -217,6 +491,353 @@ fn expand_phrase_arguments(phrase: &str, args: &[String], config: &Config) -> St
     result
 }
 
+/// Parse a date argument such as `2024-01-15`, `yesterday` or `7d`
+fn parse_date_arg(input: &str) -> std::result::Result<NaiveDate, String> {
+    parse_date_expression(input, Local::now().date_naive()).ok_or_else(|| {
+        format!(
+            "invalid date '{}', expected YYYY-MM-DD, today, yesterday, tomorrow, <n>d or <n>w",
+            input
+        )
+    })
+}
+
+/// Parse a `--property` argument, `KEY=VALUE` with a YAML value
+fn parse_property_arg(input: &str) -> std::result::Result<(String, serde_yaml::Value), String> {
+    properties::parse_property(input).map_err(|e| match e {
+        Error::InvalidEntry(message) => message,
+        e => e.to_string(),
+    })
+}
+
+/// Parse a `--column` argument, `COLUMN=VALUE`
+fn parse_column_arg(input: &str) -> std::result::Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((column, value)) if !column.trim().is_empty() => {
+            Ok((column.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("invalid column '{}', expected COLUMN=VALUE", input)),
+    }
+}
+
+/// Parse the entry given to `--at` or `--done`
+fn parse_selector_arg(input: &str) -> std::result::Result<amend::EntrySelector, String> {
+    input.parse().map_err(|_| {
+        format!(
+            "invalid entry '{}', expected a time (14:30:00), an index (2) or 'last'",
+            input
+        )
+    })
+}
+
+/// Map library errors to the sysexits.h codes that fit them. An editor that
+/// exits with an error has already reported it, so that exits with 1.
+fn exit_code(error: &Error) -> i32 {
+    match error {
+        Error::InvalidPattern { .. } => 64,
+        Error::InvalidEntry(_)
+        | Error::FrontMatter { .. }
+        | Error::PhraseNotFound(_)
+        | Error::EntryNotFound(_) => 65,
+        Error::Template { .. } => 66,
+        Error::EditorLaunch { .. } => 69,
+        Error::EditorFailed { .. } => 1,
+        Error::Io { .. } => 74,
+        Error::ConfigParse { .. } | Error::ConfigInvalid { .. } => 78,
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    // Handle version flag
+    if cli.version {
+        println!("obsidian-logging {}", env!("CARGO_PKG_VERSION"));
+        return;
+    }
+
+    let silent = cli.silent;
+    if let Err(e) = run(cli) {
+        if !silent {
+            eprintln!("Error: {}", e);
+        }
+        std::process::exit(exit_code(&e));
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    if cli.check_config {
+        let files = config_files(cli.config.as_deref());
+        let config = check_config_files(&files, cli.profile.as_deref())?;
+        if !cli.silent {
+            if files.is_empty() {
+                println!("No configuration file found, using the defaults: OK");
+            }
+            for path in files {
+                println!("{}: OK", path.display());
+            }
+            if let Some(profile) = &config.profile {
+                println!("Profile '{}': OK", profile);
+            }
+        }
+        return Ok(());
+    }
+
+    if cli.init {
+        let path = init_config_path(&cli)?;
+        init::run_init(&path, &mut io::stdin().lock(), &mut io::stdout())?;
+        return Ok(());
+    }
+
+    if needs_first_run_setup(&cli) {
+        let path = init_config_path(&cli)?;
+        println!(
+            "No configuration found. Answer a few questions to create {}",
+            path.display()
+        );
+        init::run_init(&path, &mut io::stdin().lock(), &mut io::stdout())?;
+    }
+
+    let mut config = Config::initialize_from(cli.config.as_deref(), cli.profile.as_deref())?;
+    // On stderr, so that listed and exported entries are not affected
+    if let Some(profile) = config.profile.as_deref().filter(|_| !cli.silent) {
+        eprintln!(
+            "{}",
+            config
+                .messages()?
+                .format("using_profile", &[("profile", &profile)])
+        );
+    }
+    config.ensure_vault()?;
+
+    // Apply format overrides if specified
+    if let Some(list_type) = cli.list_type {
+        config = config.with_list_type(list_type.into());
+    }
+
+    if let Some(time_format) = cli.time_format {
+        config = config.with_time_format(time_format.into());
+    }
+
+    // Handle phrase expansion if specified
+    let entry_text = if let Some(phrase_key) = &cli.phrase {
+        // Expand arguments in the phrase
+        let phrase_value = config.get_phrase(phrase_key)?;
+        expand_phrase_arguments(phrase_value, &cli.entry, &config)
+    } else if !cli.entry.is_empty() {
+        cli.entry.join(" ")
+    } else {
+        String::new()
+    };
+
+    // Resolve the target day from --date or -b
+    let today = Local::now().date_naive();
+    let days_ago = match cli.date {
+        Some(date) => (today - date).num_days(),
+        None => cli.days_ago,
+    };
+    let category = cli.category.first().map(|s| s.as_str());
+    let format: OutputFormat = cli.output.map(Into::into).unwrap_or_default();
+
+    // Determine the command to execute
+    if let Some(pattern) = &cli.search {
+        let options = search::SearchOptions {
+            ignore_case: cli.ignore_case,
+            regex: cli.regex,
+            limit: cli.limit,
+            oldest_first: cli.oldest_first,
+            categories: cli.category.clone(),
+        };
+        search::search_logs(pattern, &options, &config, cli.silent)
+            .map(|warnings| print_warnings(&warnings, cli.silent))
+    } else if let Some(from) = cli.from {
+        // List a range of days
+        list::list_log_for_range(
+            from,
+            cli.to.unwrap_or(today),
+            &config,
+            cli.silent,
+            cli.header,
+            &cli.category,
+            format,
+            cli.tasks.map(Into::into),
+        )
+    } else if let Some(selector) = cli.at {
+        // Change or delete an existing entry
+        let amendment = if cli.delete {
+            amend::Amendment::Remove
+        } else {
+            let mut words = entry_parts(cli.stdin, entry_text)?;
+            let time =
+                match cli.time {
+                    Some(time) => {
+                        let mut time_args = vec![time.clone()];
+                        time_args.extend(words);
+                        let (parsed, rest) = add::split_time_argument(time_args.into_iter())?;
+                        words = rest;
+                        Some(parsed.ok_or_else(|| {
+                            Error::InvalidEntry(format!("invalid time '{}'", time))
+                        })?)
+                    }
+                    None => None,
+                };
+            amend::Amendment::Update {
+                text: Some(words.join(" ")).filter(|t| !t.is_empty()),
+                time,
+            }
+        };
+        amend::amend_entry(
+            today - Duration::days(days_ago),
+            selector,
+            amendment,
+            &config,
+            cli.silent,
+            category,
+        )
+        .map(|warnings| print_warnings(&warnings, cli.silent))
+    } else if let Some(selector) = cli.done {
+        // Tick off a task
+        amend::amend_entry(
+            today - Duration::days(days_ago),
+            selector,
+            amend::Amendment::Complete {
+                completed_at: cli.stamp.then(|| Local::now().naive_local()),
+            },
+            &config,
+            cli.silent,
+            category,
+        )
+        .map(|warnings| print_warnings(&warnings, cli.silent))
+    } else if cli.edit {
+        // Edit command
+        edit::edit_log_for_day(days_ago, &config, category)
+            .map(|warnings| print_warnings(&warnings, cli.silent))
+    } else if cli.list {
+        // List command
+        list::list_log_for_day(
+            days_ago,
+            &config,
+            cli.silent,
+            cli.header,
+            &cli.category,
+            format,
+            cli.tasks.map(Into::into),
+        )
+    } else if cli.stdin || !entry_text.is_empty() {
+        let entry_words = entry_parts(cli.stdin, entry_text)?;
+
+        let (time, sentence_parts) = match cli.time {
+            // Handle with specific time - include all entry words
+            Some(time) => {
+                let mut time_args = vec![time];
+                time_args.extend(entry_words);
+                add::split_time_argument(time_args.into_iter())?
+            }
+            None => (None, entry_words),
+        };
+
+        let date = today - Duration::days(days_ago);
+        let warnings = add::add_entry_with_columns(
+            date,
+            sentence_parts,
+            time,
+            cli.task,
+            &cli.column,
+            &config,
+            cli.silent,
+            category,
+        )?;
+        print_warnings(&warnings, cli.silent);
+        if cli.property.is_empty() {
+            return Ok(());
+        }
+        properties::set_properties(date, &cli.property, &config, cli.silent, category)
+            .map(|warnings| print_warnings(&warnings, cli.silent))
+    } else if !cli.property.is_empty() {
+        properties::set_properties(
+            today - Duration::days(days_ago),
+            &cli.property,
+            &config,
+            cli.silent,
+            category,
+        )
+        .map(|warnings| print_warnings(&warnings, cli.silent))
+    } else {
+        // Default: list today's entries
+        list::list_log_for_day(
+            days_ago,
+            &config,
+            cli.silent,
+            cli.header,
+            &cli.category,
+            format,
+            cli.tasks.map(Into::into),
+        )
+    }
+}
+
+/// Report the problems that did not stop a command on stderr
+fn print_warnings(warnings: &[Warning], silent: bool) {
+    if silent {
+        return;
+    }
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
+/// Where `--init` writes the configuration: the `--config` path or the user configuration
+fn init_config_path(cli: &Cli) -> Result<PathBuf> {
+    cli.config
+        .clone()
+        .or_else(user_config_path)
+        .ok_or_else(|| Error::ConfigInvalid {
+            path: None,
+            problems: vec![
+                "no configuration directory is known (set HOME or XDG_CONFIG_HOME, or use --config)"
+                    .to_string(),
+            ],
+        })
+}
+
+/// Run the setup on first use: no configuration file and no vault from the
+/// environment, and someone at the terminal to answer the questions
+fn needs_first_run_setup(cli: &Cli) -> bool {
+    !cli.silent
+        && !cli.stdin
+        && config_files(cli.config.as_deref()).is_empty()
+        && env::var_os("OBSIDIAN_VAULT_DIR").is_none()
+        && io::stdin().is_terminal()
+        && io::stdout().is_terminal()
+}
+
+/// Split the entry text into words for processing. Text read from stdin is
+/// kept in one piece so that multi-line entries keep their line breaks.
+fn entry_parts(stdin: bool, entry_text: String) -> Result<Vec<String>> {
+    if stdin {
+        Ok(vec![read_stdin()?])
+    } else {
+        Ok(entry_text
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect())
+    }
+}
+
+/// Read an entry from stdin, failing if nothing was read
+fn read_stdin() -> Result<String> {
+    let mut stdin_content = String::new();
+    io::stdin()
+        .read_to_string(&mut stdin_content)
+        .map_err(|e| Error::io("<stdin>", e))?;
+
+    if stdin_content.trim().is_empty() {
+        return Err(Error::InvalidEntry(
+            "no content read from stdin".to_string(),
+        ));
+    }
+    Ok(stdin_content)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +859,15 @@ mod tests {
             event_label: "".to_string(),
             category_headers: HashMap::new(),
             phrases: HashMap::new(),
+            profile: None,
+            periodic_notes: std::collections::HashMap::new(),
+            template_variables: std::collections::HashMap::new(),
+            front_matter: Default::default(),
+            translations_dir: None,
+            entry_format: None,
+            table_columns: Vec::new(),
+            section_patterns: std::collections::HashMap::new(),
+            section_end: None,
         };
 
         // Test basic expansion
@@ -289,116 +919,3 @@ mod tests {
         assert_eq!(result, "No placeholders here");
     }
 }
-
-fn main() {
-    let cli = Cli::parse();
-
-    // Handle version flag
-    if cli.version {
-        println!("obsidian-logging {}", env!("CARGO_PKG_VERSION"));
-        return;
-    }
-
-    let mut config = Config::initialize();
-
-    // Apply format overrides if specified
-    if let Some(list_type) = cli.list_type {
-        config = config.with_list_type(list_type.into());
-    }
-
-    if let Some(time_format) = cli.time_format {
-        config = config.with_time_format(time_format.into());
-    }
-
-    // Handle phrase expansion if specified
-    let entry_text = if let Some(phrase_key) = &cli.phrase {
-        if let Some(phrase_value) = config.phrases.get(phrase_key) {
-            // Expand arguments in the phrase
-            expand_phrase_arguments(phrase_value, &cli.entry, &config)
-        } else {
-            eprintln!("Error: Phrase '{}' not found in configuration", phrase_key);
-            std::process::exit(1);
-        }
-    } else if !cli.entry.is_empty() {
-        cli.entry.join(" ")
-    } else {
-        String::new()
-    };
-
-    // Determine the command to execute
-    if cli.edit {
-        // Edit command
-        edit::edit_log_for_day(cli.days_ago, &config, cli.silent);
-    } else if cli.list {
-        // List command
-        list::list_log_for_day(cli.days_ago, &config, cli.silent, cli.header, &cli.category);
-    } else if cli.stdin {
-        // Read entry from stdin
-        let mut stdin_content = String::new();
-        if let Err(e) = io::stdin().read_to_string(&mut stdin_content) {
-            eprintln!("Error reading from stdin: {}", e);
-            std::process::exit(1);
-        }
-
-        let entry = stdin_content.trim();
-        if entry.is_empty() {
-            eprintln!("Error: No content read from stdin");
-            std::process::exit(1);
-        }
-
-        // Split the entry into words for processing
-        let entry_words: Vec<String> = entry.split_whitespace().map(|s| s.to_string()).collect();
-
-        if let Some(time) = cli.time {
-            // Handle with specific time - include all entry words
-            let mut time_args = vec![time];
-            time_args.extend(entry_words);
-            add::handle_with_time(
-                time_args.into_iter(),
-                &config,
-                cli.silent,
-                cli.category.first().map(|s| s.as_str()),
-            );
-        } else {
-            // Handle plain entry
-            let mut args = entry_words.into_iter();
-            if let Some(first) = args.next() {
-                add::handle_plain_entry(
-                    first,
-                    args,
-                    &config,
-                    cli.silent,
-                    cli.category.first().map(|s| s.as_str()),
-                );
-            }
-        }
-    } else if !entry_text.is_empty() {
-        // Add entry command
-        if let Some(time) = cli.time {
-            // Handle with specific time - include all entry words
-            let mut time_args = vec![time];
-            time_args.extend(entry_text.split_whitespace().map(|s| s.to_string()));
-            add::handle_with_time(
-                time_args.into_iter(),
-                &config,
-                cli.silent,
-                cli.category.first().map(|s| s.as_str()),
-            );
-        } else {
-            // Handle plain entry
-            let mut args = entry_text.split_whitespace().map(|s| s.to_string());
-            if let Some(first) = args.next() {
-                add::handle_plain_entry(
-                    first,
-                    args,
-                    &config,
-                    cli.silent,
-                    cli.category.first().map(|s| s.as_str()),
-                );
-            }
-        }
-    } else {
-        // Default: list today's entries
-        list::list_log_for_day(cli.days_ago, &config, cli.silent, cli.header, &cli.category);
-    }
-}
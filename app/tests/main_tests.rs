@@ -1,14 +1,20 @@
+// These tests predate the lints
+#![allow(
+    clippy::single_component_path_imports,
+    clippy::unnecessary_to_owned,
+    clippy::useless_vec
+)]
+
 use assert_cmd::cargo;
 use chrono::Datelike;
 use obsidian_logging::config::{Config, ListType, TimeFormat};
 use serde_yaml;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
 use std::str::FromStr;
 use tempfile::TempDir;
 
-fn setup_test_env() -> (PathBuf, Config) {
+fn setup_test_env() -> (TempDir, Config) {
     let temp_dir = TempDir::new().unwrap();
     let config_dir = temp_dir.path().to_path_buf();
 
@@ -40,13 +46,36 @@ fn setup_test_env() -> (PathBuf, Config) {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     let config_path = config_dir_path.join("obsidian-logging.yaml");
     let yaml = serde_yaml::to_string(&test_config).unwrap();
     fs::write(&config_path, yaml).unwrap();
 
-    (config_dir, test_config)
+    (temp_dir, test_config)
+}
+
+/// The binary, run with the configuration and vault of [`setup_test_env`].
+/// The environment is set on the command, since other tests change that of
+/// the test process.
+fn command(temp_dir: &TempDir) -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env("HOME", temp_dir.path())
+        .env("APPDATA", temp_dir.path())
+        .env("OBSIDIAN_VAULT_DIR", temp_dir.path())
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("OBSIDIAN_LOGGING_CONFIG")
+        .env_remove("OBSIDIAN_LOGGING_PROFILE");
+    cmd
 }
 
 #[test]
@@ -148,8 +177,7 @@ fn test_time_format_with_back_flag() {
 fn test_time_option_preserves_all_words() {
     let (temp_dir, _config) = setup_test_env();
 
-    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
-    cmd.env("OBSIDIAN_VAULT_DIR", &temp_dir);
+    let mut cmd = command(&temp_dir);
     let output = cmd
         .args(["--time", "14:30", "This", "is", "a", "test", "entry"])
         .output()
@@ -163,11 +191,9 @@ fn test_time_option_preserves_all_words() {
     let month = today.month();
     let day = today.day();
 
-    // Use the default file path format from config
+    // Use the file path format from config
     let file_path = temp_dir
-        .join("10-Journal")
-        .join(year.to_string())
-        .join(format!("{:02}", month))
+        .path()
         .join(format!("{}-{:02}-{:02}.md", year, month, day));
 
     assert!(file_path.exists());
@@ -200,3 +226,645 @@ fn test_version_flags() {
     assert!(stdout.contains("obsidian-logging"));
     assert!(stdout.contains(expected_version));
 }
+
+#[test]
+fn test_date_option_adds_to_given_date() {
+    let (temp_dir, _config) = setup_test_env();
+
+    let mut cmd = command(&temp_dir);
+    let output = cmd
+        .args(["--date", "2024-01-15", "-t", "21:00", "Forgot", "this"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let file_path = temp_dir.path().join("2024-01-15.md");
+
+    assert!(file_path.exists());
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(content.contains("21:00:00 Forgot this"));
+}
+
+#[test]
+fn test_since_lists_entries_grouped_by_date() {
+    let (temp_dir, _config) = setup_test_env();
+    let today = chrono::Local::now().date_naive();
+
+    for (days_ago, text) in [(0, "Today entry"), (2, "Older entry"), (9, "Too old")] {
+        let date = today - chrono::Duration::days(days_ago);
+        fs::write(
+            temp_dir.path().join(format!("{}.md", date)),
+            format!("## Test\n\n* 09:00:00 {}\n", text),
+        )
+        .unwrap();
+    }
+
+    let mut cmd = command(&temp_dir);
+    let output = cmd.args(["--since", "7d"]).output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let older = stdout
+        .find(&format!(
+            "Log entries for {}:",
+            today - chrono::Duration::days(2)
+        ))
+        .unwrap();
+    let newer = stdout.find(&format!("Log entries for {}:", today)).unwrap();
+    assert!(older < newer);
+    assert!(stdout.contains("Older entry"));
+    assert!(stdout.contains("Today entry"));
+    assert!(!stdout.contains("Too old"));
+}
+
+#[test]
+fn test_output_csv_for_range() {
+    let (temp_dir, _config) = setup_test_env();
+    let today = chrono::Local::now().date_naive();
+    let yesterday = today - chrono::Duration::days(1);
+
+    for (date, text) in [(yesterday, "Deploy, then rollback"), (today, "Standup")] {
+        fs::write(
+            temp_dir.path().join(format!("{}.md", date)),
+            format!("## Test\n\n* 09:00:00 {}\n", text),
+        )
+        .unwrap();
+    }
+
+    let mut cmd = command(&temp_dir);
+    let output = cmd.args(["--since", "1d", "-o", "csv"]).output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        format!(
+            "date,time,category,section,text\n\
+             {},09:00:00,,## Test,\"Deploy, then rollback\"\n\
+             {},09:00:00,,## Test,Standup\n",
+            yesterday, today
+        )
+    );
+}
+
+#[test]
+fn test_output_jsonl_for_missing_day_is_empty() {
+    let (temp_dir, _config) = setup_test_env();
+
+    let mut cmd = command(&temp_dir);
+    let output = cmd.args(["-l", "-o", "jsonl"]).output().unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_at_changes_and_deletes_entries() {
+    let (temp_dir, _config) = setup_test_env();
+    let today = chrono::Local::now().date_naive();
+    let file_path = temp_dir.path().join(format!("{}.md", today));
+    fs::write(
+        &file_path,
+        "## Test\n\n* 09:00:00 Standup\n* 10:00:00 Revew PR\n",
+    )
+    .unwrap();
+
+    let mut cmd = command(&temp_dir);
+    let output = cmd
+        .args(["--at", "last", "-t", "10:15", "Review", "PR"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Changed: 10:00:00 Revew PR -> 10:15:00 Review PR\n"
+    );
+
+    let mut cmd = command(&temp_dir);
+    let output = cmd.args(["--at", "09:00", "--delete"]).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(&file_path).unwrap(),
+        "## Test\n\n* 10:15:00 Review PR\n"
+    );
+
+    let mut cmd = command(&temp_dir);
+    let output = cmd.args(["--at", "5", "--delete"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(65));
+}
+
+#[test]
+fn test_invalid_config_fails_with_location() {
+    let home = TempDir::new().unwrap();
+    let vault = TempDir::new().unwrap();
+    let config_dir = home.path().join(".config").join("obsidian-logging");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("obsidian-logging.yaml"),
+        "vault: /tmp\nlist_type: tabel\n",
+    )
+    .unwrap();
+
+    for args in [vec!["Some", "entry"], vec!["--check-config"]] {
+        let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+        cmd.env("HOME", home.path())
+            .env("APPDATA", home.path())
+            .env("OBSIDIAN_VAULT_DIR", vault.path());
+        let output = cmd.args(args).output().unwrap();
+
+        assert_eq!(output.status.code(), Some(78));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("obsidian-logging.yaml:2:12: list_type: Invalid list type 'tabel'"));
+    }
+    assert_eq!(fs::read_dir(vault.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_missing_vault_is_refused() {
+    let home = TempDir::new().unwrap();
+    let missing = home.path().join("no-such-vault");
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env("HOME", home.path())
+        .env("APPDATA", home.path())
+        .env("OBSIDIAN_VAULT_DIR", &missing);
+    let output = cmd.args(["Some", "entry"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(78));
+    assert!(!missing.exists());
+}
+
+#[test]
+fn test_project_config_is_layered_without_home() {
+    let project = TempDir::new().unwrap();
+    let subdir = project.path().join("src").join("deep");
+    fs::create_dir_all(&subdir).unwrap();
+    fs::create_dir_all(project.path().join("notes")).unwrap();
+    fs::write(
+        project.path().join(".obsidian-logging.yaml"),
+        "vault: notes\nfile_path_format: \"{date}.md\"\nsection_header: \"## Project\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.current_dir(&subdir)
+        .env_remove("HOME")
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("OBSIDIAN_VAULT_DIR")
+        .env_remove("OBSIDIAN_LOGGING_CONFIG");
+    let output = cmd
+        .args(["-t", "09:00", "Worked", "on", "it"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let today = chrono::Local::now().date_naive();
+    let note = project.path().join("notes").join(format!("{}.md", today));
+    assert!(fs::read_to_string(note)
+        .unwrap()
+        .ends_with("## Project\n\n* 09:00:00 Worked on it\n"));
+}
+
+#[test]
+fn test_config_flag_selects_file() {
+    let dir = TempDir::new().unwrap();
+    let vault = dir.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    let config_path = dir.path().join("custom.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            "vault: {}\nfile_path_format: \"{{date}}.md\"\n",
+            vault.display()
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env_remove("OBSIDIAN_VAULT_DIR");
+    let output = cmd
+        .arg("--config")
+        .arg(&config_path)
+        .arg("--check-config")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}: OK\n", config_path.display())
+    );
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env_remove("OBSIDIAN_VAULT_DIR");
+    let output = cmd
+        .arg("--config")
+        .arg(dir.path().join("missing.yaml"))
+        .arg("entry")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(74));
+}
+
+#[test]
+fn test_profile_flag_selects_vault() {
+    let dir = TempDir::new().unwrap();
+    for vault in ["private", "work"] {
+        fs::create_dir_all(dir.path().join(vault)).unwrap();
+    }
+    let config_path = dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        "vault: private\nfile_path_format: \"{date}.md\"\ndefault_profile: private\nprofiles:\n  private:\n  work:\n    vault: work\n    section_header: \"## Work\"\n",
+    )
+    .unwrap();
+    let today = chrono::Local::now().date_naive();
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env_remove("OBSIDIAN_VAULT_DIR")
+        .env_remove("OBSIDIAN_LOGGING_PROFILE");
+    let output = cmd
+        .arg("--config")
+        .arg(&config_path)
+        .args(["--profile", "work", "-t", "09:00", "Standup"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Using profile 'work'\n"
+    );
+    let note = dir.path().join("work").join(format!("{}.md", today));
+    assert!(fs::read_to_string(note)
+        .unwrap()
+        .ends_with("## Work\n\n* 09:00:00 Standup\n"));
+    assert!(!dir
+        .path()
+        .join("private")
+        .join(format!("{}.md", today))
+        .exists());
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env_remove("OBSIDIAN_VAULT_DIR")
+        .env("OBSIDIAN_LOGGING_PROFILE", "home");
+    let output = cmd
+        .arg("--config")
+        .arg(&config_path)
+        .arg("--check-config")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(78));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("profile 'home' is not defined (available: private, work)"));
+}
+
+#[test]
+fn test_init_creates_config() {
+    let dir = TempDir::new().unwrap();
+    let vault = dir.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    let config_path = dir.path().join("obsidian-logging.yaml");
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env_remove("OBSIDIAN_VAULT_DIR");
+    let output = cmd
+        .arg("--config")
+        .arg(&config_path)
+        .arg("--init")
+        .write_stdin(format!("{}\n{{date}}.md\n\n\n\n\n", vault.display()))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env_remove("OBSIDIAN_VAULT_DIR");
+    let output = cmd
+        .arg("--config")
+        .arg(&config_path)
+        .args(["-t", "09:00", "First", "entry"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let note = vault.join(format!("{}.md", chrono::Local::now().date_naive()));
+    assert!(fs::read_to_string(note)
+        .unwrap()
+        .ends_with("## 🕗\n\n* 09:00:00 First entry\n"));
+}
+
+#[test]
+fn test_periodic_notes_are_listed_once() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            "vault: {}\nfile_path_format: \"{{date}}.md\"\nsection_header_retro: \"## Retro\"\nperiodic_notes:\n  weekly:\n    file_path_format: \"{{week_year}}-W{{week}}.md\"\n    categories: [retro]\n",
+            dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+        cmd.env_remove("OBSIDIAN_VAULT_DIR")
+            .env_remove("OBSIDIAN_LOGGING_PROFILE")
+            .arg("--config")
+            .arg(&config_path)
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    assert!(run(&["--date", "2026-10-14", "-t", "09:00", "Standup"])
+        .status
+        .success());
+    assert!(run(&[
+        "--date",
+        "2026-10-14",
+        "-c",
+        "retro",
+        "-t",
+        "15:00",
+        "Less",
+        "meetings"
+    ])
+    .status
+    .success());
+    assert!(dir.path().join("2026-W42.md").exists());
+
+    let output = run(&[
+        "--from",
+        "2026-10-13",
+        "--to",
+        "2026-10-16",
+        "-c",
+        "all",
+        "-o",
+        "csv",
+    ]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "date,time,category,section,text\n\
+         2026-10-14,09:00:00,,## 🕗,Standup\n\
+         2026-10-14,15:00:00,retro,## Retro,Less meetings\n"
+    );
+}
+
+#[test]
+fn test_list_day_of_periodic_note() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            "vault: {}\nfile_path_format: \"{{date}}.md\"\nsection_header_retro: \"## Retro\"\nperiodic_notes:\n  weekly:\n    file_path_format: \"{{week_year}}-W{{week}}.md\"\n    categories: [retro]\n",
+            dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+        cmd.env_remove("OBSIDIAN_VAULT_DIR")
+            .env_remove("OBSIDIAN_LOGGING_PROFILE")
+            .arg("--config")
+            .arg(&config_path)
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    for (date, text) in [
+        ("2026-10-12", "Monday"),
+        ("2026-10-14", "Wednesday"),
+        ("2026-10-16", "Friday"),
+    ] {
+        assert!(run(&["--date", date, "-c", "retro", "-t", "09:00", text])
+            .status
+            .success());
+    }
+
+    let output = run(&["--date", "2026-10-14", "-l", "-c", "retro", "-o", "csv"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "date,time,category,section,text\n\
+         2026-10-14,09:00:00,retro,## Retro,Wednesday\n"
+    );
+}
+
+#[test]
+fn test_all_categories_are_listed_by_name() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            "vault: {}\nfile_path_format: \"{{date}}.md\"\nsection_header_zeta: \"## Zeta\"\nsection_header_alpha: \"## Alpha\"\nsection_header_mid: \"## Mid\"\n",
+            dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+        cmd.env_remove("OBSIDIAN_VAULT_DIR")
+            .env_remove("OBSIDIAN_LOGGING_PROFILE")
+            .arg("--config")
+            .arg(&config_path)
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    for category in ["zeta", "mid", "alpha"] {
+        assert!(run(&[
+            "--date",
+            "2026-10-14",
+            "-c",
+            category,
+            "-t",
+            "09:00",
+            category
+        ])
+        .status
+        .success());
+    }
+
+    let output = run(&["--date", "2026-10-14", "-l", "-c", "all", "-o", "csv"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "date,time,category,section,text\n\
+         2026-10-14,09:00:00,alpha,## Alpha,alpha\n\
+         2026-10-14,09:00:00,mid,## Mid,mid\n\
+         2026-10-14,09:00:00,zeta,## Zeta,zeta\n"
+    );
+}
+
+#[test]
+fn test_property_flag_sets_front_matter() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            "vault: {}\nfile_path_format: \"{{date}}.md\"\nsection_header: \"## Log\"\nfront_matter:\n  entries: entries\n",
+            dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+        cmd.env_remove("OBSIDIAN_VAULT_DIR")
+            .env_remove("OBSIDIAN_LOGGING_PROFILE")
+            .arg("--config")
+            .arg(&config_path)
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let output = run(&["--date", "2026-10-14", "--property", "mood=good"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Set mood.\n");
+
+    let output = run(&[
+        "--date",
+        "2026-10-14",
+        "--property",
+        "energy=4",
+        "-t",
+        "09:00",
+        "Standup",
+    ]);
+    assert!(output.status.success());
+
+    let content = fs::read_to_string(dir.path().join("2026-10-14.md")).unwrap();
+    assert!(content.starts_with("---\nmood: good\nentries: 1\nenergy: 4\n---\n"));
+    assert!(content.contains("09:00:00 Standup"));
+
+    let output = run(&["--property", "mood"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_locale_translates_labels_and_messages() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            "vault: {}\nfile_path_format: \"{{date}}.md\"\nsection_header: \"## Log\"\nlist_type: table\nlocale: de_DE\ntranslations_dir: {}\n",
+            dir.path().display(),
+            dir.path().join("translations").display()
+        ),
+    )
+    .unwrap();
+
+    let output = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"))
+        .env_remove("OBSIDIAN_VAULT_DIR")
+        .env_remove("OBSIDIAN_LOGGING_PROFILE")
+        .arg("--config")
+        .arg(&config_path)
+        .args(["--date", "2026-10-14", "-t", "09:00", "Standup"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Protokolliert.\n");
+
+    let content = fs::read_to_string(dir.path().join("2026-10-14.md")).unwrap();
+    assert!(content.contains("| Zeit | Ereignis |"));
+}
+
+#[test]
+fn test_task_flags() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            "vault: {}\nfile_path_format: \"{{date}}.md\"\nsection_header: \"## Log\"\n",
+            dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+        cmd.env_remove("OBSIDIAN_VAULT_DIR")
+            .env_remove("OBSIDIAN_LOGGING_PROFILE")
+            .arg("--config")
+            .arg(&config_path)
+            .arg("--date")
+            .arg("2026-10-14")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    assert!(run(&["--task", "-t", "09:00", "Pay rent"]).status.success());
+    assert!(run(&["--task", "-t", "10:00", "Call the bank"])
+        .status
+        .success());
+    assert!(run(&["-t", "11:00", "Lunch"]).status.success());
+
+    let output = run(&["--done", "1", "--stamp"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("Done: 09:00:00 Pay rent ✅ "));
+
+    let content = fs::read_to_string(dir.path().join("2026-10-14.md")).unwrap();
+    assert!(content.contains("- [x] 09:00:00 Pay rent ✅ "));
+    assert!(content.contains("- [ ] 10:00:00 Call the bank\n* 11:00:00 Lunch\n"));
+
+    let output = run(&["--tasks", "open"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Log entries for 2026-10-14:\n- [ ] 10:00:00 Call the bank\n"
+    );
+
+    // The last task is ticked off, though the last entry is not a task
+    let output = run(&["--done", "last"]);
+    assert!(output.status.success());
+    let content = fs::read_to_string(dir.path().join("2026-10-14.md")).unwrap();
+    assert!(content.contains("- [x] 10:00:00 Call the bank\n* 11:00:00 Lunch\n"));
+
+    let output = run(&["--done", "3"]);
+    assert_eq!(output.status.code(), Some(65));
+}
+
+#[test]
+fn test_column_flag() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            "vault: {}\nfile_path_format: \"{{date}}.md\"\nsection_header: \"## Log\"\nlist_type: table\ntable_columns: [End, Duration]\n",
+            dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+        cmd.env_remove("OBSIDIAN_VAULT_DIR")
+            .env_remove("OBSIDIAN_LOGGING_PROFILE")
+            .arg("--config")
+            .arg(&config_path)
+            .arg("--date")
+            .arg("2026-10-14")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let output = run(&["-t", "13:00", "--column", "end=17:15", "Client", "workshop"]);
+    assert!(output.status.success());
+
+    let content = fs::read_to_string(dir.path().join("2026-10-14.md")).unwrap();
+    assert!(content.contains("| 13:00:00 | Client workshop | 17:15 | 4:15 |"));
+
+    let output = run(&["--column", "endtime", "Review"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&["--column", "author=me", "Review"]);
+    assert_eq!(output.status.code(), Some(65));
+}
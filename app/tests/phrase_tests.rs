@@ -1,3 +1,6 @@
+// These tests predate the lints
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use assert_cmd::cargo;
 use obsidian_logging::config::{Config, ListType, TimeFormat};
 use std::collections::HashMap;
@@ -76,6 +79,15 @@ fn setup_test_env_with_phrases() -> (TempDir, Config) {
         event_label: "Hendelse".to_string(),
         category_headers: HashMap::new(),
         phrases,
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
     (temp_dir, config)
 }
@@ -206,6 +218,7 @@ fn test_phrase_not_found() {
 
         let output = cmd.output().unwrap();
         assert!(!output.status.success());
+        assert_eq!(output.status.code(), Some(65));
 
         let stderr = String::from_utf8_lossy(&output.stderr);
         assert!(stderr.contains("Phrase 'nonexistent' not found"));
@@ -316,6 +329,15 @@ fn test_phrase_argument_expansion_with_placeholders() {
         event_label: "Hendelse".to_string(),
         category_headers: HashMap::new(),
         phrases,
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     // Create the config file
@@ -388,6 +410,15 @@ fn test_phrase_argument_expansion_with_time() {
         event_label: "Hendelse".to_string(),
         category_headers: HashMap::new(),
         phrases,
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     // Create the config file
@@ -443,6 +474,15 @@ fn test_phrase_hash_placeholder_expansion() {
         event_label: "Hendelse".to_string(),
         category_headers: HashMap::new(),
         phrases,
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     // Create the config file
@@ -512,6 +552,15 @@ fn test_phrase_hash_placeholder_with_norwegian_conjunction() {
         event_label: "Hendelse".to_string(),
         category_headers: HashMap::new(),
         phrases,
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     // Create the config file
@@ -1,3 +1,4 @@
+use assert_cmd::cargo;
 use chrono::Local;
 use obsidian_logging::commands::add::{handle_plain_entry, handle_with_time};
 use obsidian_logging::config::{Config, ListType, TimeFormat};
@@ -18,6 +19,15 @@ fn setup_test_env() -> (TempDir, Config) {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
     (temp_dir, config)
 }
@@ -39,7 +49,7 @@ fn test_stdin_functionality() {
     // Process the entry
     let mut args = entry_words.into_iter();
     if let Some(first) = args.next() {
-        handle_plain_entry(first, args, &config, false, None);
+        handle_plain_entry(first, args, &config, false, None).unwrap();
     }
 
     // Verify the entry was written
@@ -64,7 +74,7 @@ fn test_stdin_with_time_override() {
     // Process the entry with time override (simulating -t 14:30)
     let mut time_args = vec!["14:30".to_string()];
     time_args.extend(entry_words);
-    handle_with_time(time_args.into_iter(), &config, false, None);
+    handle_with_time(time_args.into_iter(), &config, false, None).unwrap();
 
     // Verify the entry was written with the correct time (with seconds defaulting to 00)
     let content = fs::read_to_string(&file_path).unwrap();
@@ -90,7 +100,7 @@ fn test_stdin_empty_input() {
     if !entry_words.is_empty() {
         let mut args = entry_words.into_iter();
         if let Some(first) = args.next() {
-            handle_plain_entry(first, args, &config, false, None);
+            handle_plain_entry(first, args, &config, false, None).unwrap();
         }
     }
 
@@ -101,3 +111,27 @@ fn test_stdin_empty_input() {
         assert!(!content.contains("* ") || content.contains("## Test"));
     }
 }
+
+#[test]
+fn test_stdin_keeps_line_breaks() {
+    let temp_dir = TempDir::new().unwrap();
+    let today = Local::now().date_naive();
+
+    let mut cmd = assert_cmd::Command::new(cargo::cargo_bin!("obsidian-logging"));
+    cmd.env("OBSIDIAN_VAULT_DIR", temp_dir.path());
+    cmd.args(["-S", "-t", "10:00"])
+        .write_stdin("Panic in worker\nthread 'main' panicked at src/lib.rs:1:1\n    stack frame\n")
+        .assert()
+        .success();
+
+    let file_path = temp_dir
+        .path()
+        .join("10-Journal")
+        .join(today.format("%Y").to_string())
+        .join(today.format("%m").to_string())
+        .join(format!("{}.md", today));
+    let content = fs::read_to_string(file_path).unwrap();
+    assert!(content.contains(
+        "* 10:00:00 Panic in worker\n  thread 'main' panicked at src/lib.rs:1:1\n      stack frame\n"
+    ));
+}
@@ -1,65 +1,31 @@
-use crate::config::{Config, ListType};
-use crate::template::get_template_content;
-use crate::utils::{extract_log_entries, format_time, get_log_path_for_date, parse_time};
-use chrono::{Duration, Local, NaiveTime, Timelike};
-use std::fs::{create_dir_all, read_to_string, write};
-
-/// Parse a table row into (timestamp, entry)
-fn parse_table_row(line: &str) -> Option<(String, String)> {
-    let parts: Vec<&str> = line.split('|').collect();
-    if parts.len() >= 4 {
-        let time = parts[1].trim();
-        let entry = parts[2].trim();
-        if !time.is_empty() && !entry.is_empty() {
-            return Some((time.to_string(), entry.to_string()));
-        }
-    }
-    None
-}
-
-/// Parse a bullet entry into (timestamp, entry)
-fn parse_bullet_entry(line: &str) -> Option<(String, String)> {
-    let content = line.trim_start_matches(['-', '*', ' ']);
-
-    // Try to find a valid time pattern at the beginning
-    // This handles both 24-hour (HH:MM:SS) and 12-hour (HH:MM:SS AM/PM) formats
-    let time_patterns = [
-        // 24-hour format: HH:MM:SS
-        r"^(\d{1,2}:\d{2}:\d{2})\s+(.+)$",
-        // 24-hour format: HH:MM (backward compatibility)
-        r"^(\d{1,2}:\d{2})\s+(.+)$",
-        // 12-hour format: HH:MM:SS AM/PM
-        r"^(\d{1,2}:\d{2}:\d{2}\s+[AaPp][Mm])\s+(.+)$",
-        // 12-hour format: HH:MM AM/PM (backward compatibility)
-        r"^(\d{1,2}:\d{2}\s+[AaPp][Mm])\s+(.+)$",
-    ];
-
-    for pattern in &time_patterns {
-        if let Ok(regex) = regex::Regex::new(pattern) {
-            if let Some(captures) = regex.captures(content) {
-                let time = captures.get(1).unwrap().as_str().trim();
-                let entry = captures.get(2).unwrap().as_str().trim();
-                return Some((time.to_string(), entry.to_string()));
-            }
-        }
-    }
-
-    // Fallback to original behavior for backward compatibility
-    if let Some(space_pos) = content.find(' ') {
-        let (time, entry) = content.split_at(space_pos);
-        return Some((time.trim().to_string(), entry.trim().to_string()));
-    }
-
-    None
-}
+use crate::config::{Config, ListType, Period};
+use crate::document::{DailyLog, TaskStatus};
+use crate::error::{Error, Result, Warning};
+use crate::front_matter::collect_tags;
+use crate::storage::{write_atomic, VaultLock};
+use crate::utils::{format_duration, get_log_path_for_category, normalize_entry_text, parse_time};
+use chrono::{Local, NaiveDate, NaiveTime, Timelike};
+use std::fs::create_dir_all;
+use std::path::Path;
 
 pub fn handle_with_time(
-    mut args: impl Iterator<Item = String>,
+    args: impl Iterator<Item = String>,
     config: &Config,
     silent: bool,
     category: Option<&str>,
-) {
-    let time_str = args.next().expect("Expected time as first argument");
+) -> Result<Vec<Warning>> {
+    let (time, sentence_parts) = split_time_argument(args)?;
+    handle_plain_entry_with_time(sentence_parts, time, config, silent, category)
+}
+
+/// Split a leading time argument (optionally followed by AM/PM) from the entry words.
+/// If the first argument is not a valid time, it is kept as part of the entry.
+pub fn split_time_argument(
+    mut args: impl Iterator<Item = String>,
+) -> Result<(Option<NaiveTime>, Vec<String>)> {
+    let time_str = args
+        .next()
+        .ok_or_else(|| Error::InvalidEntry("expected time as first argument".to_string()))?;
     let mut sentence_parts = Vec::new();
 
     // Check if next word is AM/PM
@@ -68,15 +34,13 @@ pub fn handle_with_time(
             let time_with_period = format!("{} {}", time_str, next_word);
             if let Some(time) = parse_time(&time_with_period) {
                 sentence_parts.extend(args);
-                handle_plain_entry_with_time(sentence_parts, Some(time), config, silent, category);
-                return;
+                return Ok((Some(time), sentence_parts));
             } else {
                 // If time parsing failed with AM/PM, treat both as part of the sentence
                 sentence_parts.push(time_str);
                 sentence_parts.push(next_word);
                 sentence_parts.extend(args);
-                handle_plain_entry_with_time(sentence_parts, None, config, silent, category);
-                return;
+                return Ok((None, sentence_parts));
             }
         } else {
             sentence_parts.push(next_word);
@@ -86,12 +50,12 @@ pub fn handle_with_time(
     // Try parsing time without AM/PM
     if let Some(time) = parse_time(&time_str) {
         sentence_parts.extend(args);
-        handle_plain_entry_with_time(sentence_parts, Some(time), config, silent, category);
+        Ok((Some(time), sentence_parts))
     } else {
         // If time parsing failed, treat first argument as part of the sentence
         sentence_parts.insert(0, time_str);
         sentence_parts.extend(args);
-        handle_plain_entry_with_time(sentence_parts, None, config, silent, category);
+        Ok((None, sentence_parts))
     }
 }
 
@@ -101,10 +65,10 @@ pub fn handle_plain_entry(
     config: &Config,
     silent: bool,
     category: Option<&str>,
-) {
+) -> Result<Vec<Warning>> {
     let mut sentence_parts = vec![first_arg];
     sentence_parts.extend(args);
-    handle_plain_entry_with_time(sentence_parts, None, config, silent, category);
+    handle_plain_entry_with_time(sentence_parts, None, config, silent, category)
 }
 
 pub fn handle_plain_entry_with_time(
@@ -113,141 +77,185 @@ pub fn handle_plain_entry_with_time(
     config: &Config,
     silent: bool,
     category: Option<&str>,
-) {
-    let sentence = sentence_parts.join(" ");
+) -> Result<Vec<Warning>> {
+    let today = Local::now().date_naive();
+    add_entry_for_date(
+        today,
+        sentence_parts,
+        time_override,
+        config,
+        silent,
+        category,
+    )
+}
+
+/// Add an entry to the note for `date`, creating the note from the template if needed.
+/// Categories with a periodic note are added to the weekly, monthly or yearly note
+/// containing `date`, with the date before their time. Without a time override,
+/// the current time of day is used. Returns the warnings about the template and
+/// the front matter of the note.
+pub fn add_entry_for_date(
+    date: NaiveDate,
+    sentence_parts: Vec<String>,
+    time_override: Option<NaiveTime>,
+    config: &Config,
+    silent: bool,
+    category: Option<&str>,
+) -> Result<Vec<Warning>> {
+    add_entry_with_columns(
+        date,
+        sentence_parts,
+        time_override,
+        false,
+        &[],
+        config,
+        silent,
+        category,
+    )
+}
+
+/// Add an entry, or an open task if `task` is set, with cells for the extra
+/// table columns given as `(column label, value)`, like [`add_entry_for_date`].
+///
+/// Columns labelled `Tags`, `Category` and `Duration` that are not given are
+/// filled in from the `#tags` of the text, the category, and the time until the
+/// `End` column. Giving columns is an error if the section is not a table.
+#[allow(clippy::too_many_arguments)]
+pub fn add_entry_with_columns(
+    date: NaiveDate,
+    sentence_parts: Vec<String>,
+    time_override: Option<NaiveTime>,
+    task: bool,
+    columns: &[(String, String)],
+    config: &Config,
+    silent: bool,
+    category: Option<&str>,
+) -> Result<Vec<Warning>> {
+    let sentence = normalize_entry_text(&sentence_parts.join(" "));
+    if sentence.is_empty() {
+        return Err(Error::InvalidEntry("entry text is empty".to_string()));
+    }
     let now = Local::now();
-    let date = now.date_naive();
     let time = time_override.unwrap_or_else(|| {
         NaiveTime::from_hms_opt(now.hour(), now.minute(), now.second()).unwrap()
     });
 
-    let file_path = get_log_path_for_date(date, config);
-    create_dir_all(file_path.parent().unwrap()).expect("Could not create log directory");
-
-    let is_new_file = !file_path.exists();
-    let content = if is_new_file {
-        get_template_content(config)
-    } else {
-        read_to_string(&file_path).unwrap_or_default()
+    let file_path = get_log_path_for_category(date, config, category)?;
+    // Hold the vault lock from reading the note until the new version is in
+    // place. Taking it first also refuses a vault that does not exist.
+    let _lock = VaultLock::acquire(config)?;
+    if let Some(parent) = file_path.parent() {
+        create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+    }
+    let (mut log, is_new_file, mut warnings) = match DailyLog::load(&file_path, config)? {
+        Some(log) => (log, false, Vec::new()),
+        None => {
+            let (log, warnings) = DailyLog::from_template_for_category(config, category, date)?;
+            (log, true, warnings)
+        }
     };
 
     let section_header = config.get_section_header_for_category(category);
-    let (before_log, after_log, entries, detected_type) =
-        extract_log_entries(&content, section_header, &config.list_type, config, false);
+    let section_category = config.get_category_for_section_header(section_header);
+    let section = log.section_or_insert(section_header, section_category);
 
     // For new files, always use the config list type
     // For existing files, use detected type unless there are no entries
-    let effective_type = if is_new_file || entries.is_empty() {
-        config.list_type.clone()
+    if is_new_file {
+        section.list_type = Some(config.list_type.clone());
+    }
+
+    let columns = if section.list_type.as_ref().unwrap_or(&config.list_type) == &ListType::Table {
+        table_row(
+            &section.column_labels(config),
+            columns,
+            time,
+            &sentence,
+            category,
+        )?
+    } else if let Some((label, _)) = columns.first() {
+        return Err(Error::InvalidEntry(format!(
+            "column '{}' cannot be set, the section is not a table",
+            label
+        )));
     } else {
-        detected_type
+        Vec::new()
     };
 
-    // Parse all entries into (timestamp, entry) pairs
-    let parsed_entries: Vec<(String, String)> = entries
-        .iter()
-        .filter_map(|e| {
-            if e.starts_with("| ") {
-                parse_table_row(e)
-            } else if e.starts_with("- ") || e.starts_with("* ") {
-                parse_bullet_entry(e)
-            } else {
-                None
-            }
-        })
-        .collect();
+    // Existing entries are re-sorted and rewritten with normalized timestamps
+    let status = task.then_some(TaskStatus::Open);
+    let entry_date = (config.period_for_category(category) != Period::Daily).then_some(date);
+    section.insert_entry(entry_date, time, &sentence, status, columns);
+    warnings.extend(update_front_matter(&mut log, &file_path, config));
 
-    // Normalize all existing timestamps to the current format for consistent comparison
-    // This ensures we can properly detect duplicates even when formats differ
-    let normalized_existing: Vec<(NaiveTime, String)> = parsed_entries
-        .iter()
-        .filter_map(|(time_str, entry)| parse_time(time_str).map(|t| (t, entry.clone())))
-        .collect();
+    let new_content = log.render(config);
+    write_atomic(&file_path, &(new_content.trim_end().to_string() + "\n"))?;
+
+    if !silent {
+        println!("{}", config.messages()?.get("logged"));
+    }
 
-    // Find a unique timestamp by incrementing seconds if needed
-    let mut final_time = time;
+    Ok(warnings)
+}
 
-    // Check if timestamp already exists and increment seconds until unique
-    // Compare NaiveTime values to handle cases where formats differ
-    while normalized_existing
+/// The cells of a new table row for the column `labels`, from the given values
+/// and what can be derived from the entry
+fn table_row(
+    labels: &[String],
+    given: &[(String, String)],
+    time: NaiveTime,
+    text: &str,
+    category: Option<&str>,
+) -> Result<Vec<(String, String)>> {
+    let find = |label: &str| {
+        given
+            .iter()
+            .find(|(l, _)| l.eq_ignore_ascii_case(label))
+            .map(|(_, value)| value.clone())
+    };
+    if let Some((label, _)) = given
         .iter()
-        .any(|(existing_time, _)| *existing_time == final_time)
+        .find(|(l, _)| !labels.iter().any(|known| known.eq_ignore_ascii_case(l)))
     {
-        // Increment by 1 second using chrono's Duration
-        final_time += Duration::seconds(1);
+        return Err(Error::InvalidEntry(format!(
+            "unknown table column '{}', add it to table_columns",
+            label
+        )));
     }
 
-    // Combine existing entries (with their parsed timestamps) and the new entry,
-    // then normalize all to the current format
-    let mut all_entries: Vec<(NaiveTime, String)> = normalized_existing;
-    all_entries.push((final_time, sentence.clone()));
-
-    // Sort entries by timestamp
-    all_entries.sort_by_key(|a| a.0);
-
-    // Normalize all timestamps to include seconds and use current format
-    // This ensures existing entries without seconds get reformatted with seconds
-    let normalized_entries: Vec<(String, String)> = all_entries
+    let row = labels
         .iter()
-        .map(|(parsed_time, entry)| {
-            let normalized_time = format_time(*parsed_time, &config.time_format);
-            (normalized_time, entry.clone())
+        .filter_map(|label| {
+            let value = find(label).or_else(|| match label.to_lowercase().as_str() {
+                "tags" => {
+                    let tags = collect_tags(text);
+                    (!tags.is_empty()).then(|| {
+                        tags.iter()
+                            .map(|tag| format!("#{}", tag))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                }
+                "category" => category.map(|c| c.to_string()),
+                "duration" => find("end")
+                    .and_then(|end| parse_time(&end))
+                    .map(|end| format_duration(end - time)),
+                _ => None,
+            })?;
+            Some((label.clone(), value))
         })
         .collect();
+    Ok(row)
+}
 
-    // Format entries according to effective type
-    let formatted_entries = match effective_type {
-        ListType::Bullet => normalized_entries
-            .into_iter()
-            .map(|(time, entry)| format!("* {} {}", time, entry))
-            .collect(),
-        ListType::Table => {
-            // Calculate maximum widths
-            let mut max_time_width = config.time_label.len();
-            let mut max_entry_width = config.event_label.len();
-
-            for (time, entry) in &normalized_entries {
-                max_time_width = max_time_width.max(time.len());
-                max_entry_width = max_entry_width.max(entry.len());
-            }
-
-            // Format table
-            let mut table = Vec::new();
-            // Always show header for table format
-            table.push(format!(
-                "| {} | {} |",
-                config.time_label, config.event_label
-            ));
-            table.push(format!(
-                "| {} | {} |",
-                "-".repeat(max_time_width),
-                "-".repeat(max_entry_width)
-            ));
-            table.extend(
-                normalized_entries
-                    .into_iter()
-                    .map(|(time, entry)| format!("| {} | {} |", time, entry)),
-            );
-            table
-        }
-    };
-
-    let new_content = format!(
-        "{}{}\n\n{}\n{}",
-        before_log,
-        section_header,
-        formatted_entries.join("\n"),
-        if after_log.is_empty() {
-            String::new()
-        } else {
-            format!("\n{}", after_log)
-        }
-    );
-
-    write(&file_path, new_content.trim_end().to_string() + "\n")
-        .expect("Error writing logs to file");
-
-    if !silent {
-        println!("Logged.");
-    }
+/// Update the properties maintained in the front matter of a note before it is
+/// written. Front matter that is not valid YAML is left as it is, with a warning.
+pub(crate) fn update_front_matter(
+    log: &mut DailyLog,
+    file_path: &Path,
+    config: &Config,
+) -> Option<Warning> {
+    log.update_front_matter(config, Local::now().naive_local())
+        .err()
+        .map(|message| Warning::new(file_path, format!("front matter not updated: {}", message)))
 }
@@ -0,0 +1,191 @@
+use crate::commands::add::update_front_matter;
+use crate::config::Config;
+use crate::document::{DailyLog, Section};
+use crate::error::{Error, Result, Warning};
+use crate::storage::{write_atomic, VaultLock};
+use crate::utils::{format_time, get_log_path_for_category, normalize_entry_text, parse_time};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::str::FromStr;
+
+/// Which entry of a section to amend or delete
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntrySelector {
+    /// The entry with this timestamp. In weekly, monthly and yearly notes, the
+    /// entry must also be from the given day.
+    Time(NaiveTime),
+    /// The entry at this 1-based position, as shown when listing
+    Index(usize),
+    /// The last entry in the section, or the last task when ticking off a task
+    Last,
+}
+
+impl FromStr for EntrySelector {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("last") {
+            return Ok(EntrySelector::Last);
+        }
+        if let Ok(index) = input.parse::<usize>() {
+            if index == 0 {
+                return Err(Error::InvalidEntry("entry index starts at 1".to_string()));
+            }
+            return Ok(EntrySelector::Index(index));
+        }
+        parse_time(input).map(EntrySelector::Time).ok_or_else(|| {
+            Error::InvalidEntry(format!("'{}' is not a time, an index or 'last'", input))
+        })
+    }
+}
+
+impl EntrySelector {
+    /// Position of the selected entry in the section's entries, for the note of
+    /// `date`. In weekly, monthly and yearly notes, indices and `last` count only
+    /// the entries of that day. With `tasks_only`, `last` is the last task.
+    fn resolve(&self, section: &Section, date: NaiveDate, tasks_only: bool) -> Option<usize> {
+        let mut on_day = section
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.date.is_none_or(|d| d == date));
+        match self {
+            EntrySelector::Time(time) => on_day.find(|(_, e)| e.time == *time).map(|(i, _)| i),
+            EntrySelector::Index(index) => on_day.nth(index - 1).map(|(i, _)| i),
+            EntrySelector::Last if tasks_only => {
+                on_day.rfind(|(_, e)| e.task.is_some()).map(|(i, _)| i)
+            }
+            EntrySelector::Last => on_day.next_back().map(|(i, _)| i),
+        }
+    }
+
+    fn describe(&self, config: &Config) -> String {
+        match self {
+            EntrySelector::Time(time) => format!("at {}", format_time(*time, &config.time_format)),
+            EntrySelector::Index(index) => format!("number {}", index),
+            EntrySelector::Last => "last".to_string(),
+        }
+    }
+}
+
+/// What to do with the selected entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum Amendment {
+    /// Delete the entry
+    Remove,
+    /// Replace the text and/or the timestamp, keeping whatever is `None`
+    Update {
+        text: Option<String>,
+        time: Option<NaiveTime>,
+    },
+    /// Tick off a task, appending when it was completed if `completed_at` is set
+    Complete { completed_at: Option<NaiveDateTime> },
+}
+
+/// Delete, change or tick off an entry in the note for `date`.
+/// The section is re-sorted and re-rendered, and the change is reported unless `silent`.
+/// Returns a warning if the front matter could not be updated.
+pub fn amend_entry(
+    date: NaiveDate,
+    selector: EntrySelector,
+    amendment: Amendment,
+    config: &Config,
+    silent: bool,
+    category: Option<&str>,
+) -> Result<Vec<Warning>> {
+    let file_path = get_log_path_for_category(date, config, category)?;
+    let _lock = VaultLock::acquire(config)?;
+    let Some(mut log) = DailyLog::load(&file_path, config)? else {
+        return Err(Error::EntryNotFound(format!("no log found for {}", date)));
+    };
+
+    let section_header = config.get_section_header_for_category(category);
+    let not_found = || {
+        Error::EntryNotFound(format!(
+            "no entry {} in '{}' for {}",
+            selector.describe(config),
+            section_header,
+            date
+        ))
+    };
+    let index = log
+        .section(section_header)
+        .and_then(|section| {
+            let tasks_only = matches!(amendment, Amendment::Complete { .. });
+            selector.resolve(section, date, tasks_only)
+        })
+        .ok_or_else(not_found)?;
+    let section = log.section_mut(section_header).ok_or_else(not_found)?;
+
+    let old = section.entries[index].clone();
+    let messages = config.messages()?;
+    let message = match amendment {
+        Amendment::Remove => {
+            section.remove(index);
+            let entry = format!(
+                "{} {}",
+                format_time(old.time, &config.time_format),
+                old.text
+            );
+            messages.format("removed", &[("entry", &entry)])
+        }
+        Amendment::Update { text, time } => {
+            if text.is_none() && time.is_none() {
+                return Err(Error::InvalidEntry(
+                    "nothing to change, give new text or a new time".to_string(),
+                ));
+            }
+            let text = text.as_deref().map(normalize_entry_text);
+            if text.as_deref().is_some_and(str::is_empty) {
+                return Err(Error::InvalidEntry("entry text is empty".to_string()));
+            }
+            let new_time = section.update(index, text.as_deref(), time);
+            let old_entry = format!(
+                "{} {}",
+                format_time(old.time, &config.time_format),
+                old.text
+            );
+            let new_entry = format!(
+                "{} {}",
+                format_time(new_time, &config.time_format),
+                text.as_deref().unwrap_or(&old.text)
+            );
+            messages.format("changed", &[("old", &old_entry), ("new", &new_entry)])
+        }
+        Amendment::Complete { completed_at } => {
+            if old.task.is_none() {
+                return Err(Error::InvalidEntry(format!(
+                    "entry {} in '{}' is not a task",
+                    selector.describe(config),
+                    section_header
+                )));
+            }
+            let stamp = completed_at.map(|at| {
+                format!(
+                    "{} {}",
+                    at.format("%Y-%m-%d"),
+                    format_time(at.time(), &config.time_format)
+                )
+            });
+            section.complete(index, stamp.as_deref());
+            let entry = format!(
+                "{} {}",
+                format_time(old.time, &config.time_format),
+                section.entries[index].text
+            );
+            messages.format("done", &[("entry", &entry)])
+        }
+    };
+
+    let warnings: Vec<Warning> = update_front_matter(&mut log, &file_path, config)
+        .into_iter()
+        .collect();
+    let new_content = log.render(config);
+    write_atomic(&file_path, &(new_content.trim_end().to_string() + "\n"))?;
+
+    if !silent {
+        println!("{}", message);
+    }
+
+    Ok(warnings)
+}
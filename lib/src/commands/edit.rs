@@ -1,28 +1,58 @@
 use crate::config::Config;
-use crate::template::get_template_content;
-use crate::utils::get_log_path_for_date;
+use crate::document::DailyLog;
+use crate::error::{Error, Result, Warning};
+use crate::storage::{write_atomic, VaultLock};
+use crate::utils::get_log_path_for_category;
 use chrono::{Duration, Local};
-use std::fs::{create_dir_all, write};
+use std::fs::create_dir_all;
 use std::process::Command;
 
-pub fn edit_log_for_day(relative_day: i64, config: &Config, silent: bool) {
-    let date = Local::now().date_naive() - Duration::days(relative_day);
-    let file_path = get_log_path_for_date(date, config);
-    create_dir_all(file_path.parent().unwrap()).expect("Couldn't create parent directory");
+/// Open the note for the day `relative_day` days ago in `$EDITOR`. For a
+/// category with a periodic note, the weekly, monthly or yearly note
+/// containing that day is opened instead. Returns the warnings about the
+/// template of a new note.
+pub fn edit_log_for_day(
+    relative_day: i64,
+    config: &Config,
+    category: Option<&str>,
+) -> Result<Vec<Warning>> {
+    let today = Local::now().date_naive();
+    let date = today - Duration::days(relative_day);
+    let file_path = get_log_path_for_category(date, config, category)?;
+    // A vault that does not exist is not created
+    config.ensure_vault()?;
+    if let Some(parent) = file_path.parent() {
+        create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+    }
 
-    // Only create a new file if it's today or a future date
-    if !file_path.exists() && relative_day <= 0 {
-        let template_content = get_template_content(config);
-        write(&file_path, template_content).expect("Could not create log file from template");
+    // Only create a new file if it's for the current period or a future one
+    let period = config.period_for_category(category);
+    let mut warnings = Vec::new();
+    if period.start(date) >= period.start(today) {
+        let _lock = VaultLock::acquire(config)?;
+        if !file_path.exists() {
+            let (log, template_warnings) =
+                DailyLog::from_template_for_category(config, category, date)?;
+            warnings = template_warnings;
+            write_atomic(&file_path, &log.render(config))?;
+        }
     }
 
     let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
-    let status = Command::new(editor)
+    let status = Command::new(&editor)
         .arg(&file_path)
         .status()
-        .expect("Failed to start editor");
+        .map_err(|e| Error::EditorLaunch {
+            editor: editor.clone(),
+            source: e,
+        })?;
 
-    if !status.success() && !silent {
-        eprintln!("Editor exited with non-zero exit code");
+    if !status.success() {
+        return Err(Error::EditorFailed {
+            editor,
+            code: status.code(),
+        });
     }
+
+    Ok(warnings)
 }
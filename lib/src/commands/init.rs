@@ -0,0 +1,361 @@
+use crate::config::{Config, ListType, Period};
+use crate::error::{Error, Result};
+use crate::locale::parse_locale;
+use crate::utils::{self, expand_tilde};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+/// The settings asked for by the interactive setup
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitSettings {
+    pub vault: String,
+    pub file_path_format: String,
+    pub section_header: String,
+    pub list_type: ListType,
+    pub locale: Option<String>,
+    pub template_path: Option<String>,
+    /// Write a starter template to `template_path`, which does not exist yet
+    pub create_template: bool,
+}
+
+/// Ask for the configuration interactively and write it to `config_path`,
+/// scaffolding the template file if requested.
+///
+/// An existing configuration file is only replaced after confirmation.
+/// Returns `false` if the user declined to replace the existing file.
+pub fn run_init<R: BufRead, W: Write>(
+    config_path: &Path,
+    input: &mut R,
+    output: &mut W,
+) -> Result<bool> {
+    if config_path.exists() {
+        let question = format!(
+            "{} already exists. Replace it? [y/N]: ",
+            config_path.display()
+        );
+        if !confirm(input, output, &question, false)? {
+            return Ok(false);
+        }
+    }
+
+    let settings = prompt_settings(input, output)?;
+    write_init(config_path, &settings)?;
+
+    writeln!(output, "Wrote {}", config_path.display()).map_err(stdout_error)?;
+    if settings.create_template {
+        if let Some(template_path) = &settings.template_path {
+            writeln!(output, "Wrote {}", template_path).map_err(stdout_error)?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Ask for each setting until a valid answer is given. Empty answers take the
+/// default shown in brackets.
+pub fn prompt_settings<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+) -> Result<InitSettings> {
+    let defaults = Config::default();
+
+    let vault = loop {
+        let vault = ask(input, output, "Vault directory", Some(&defaults.vault))?;
+        match validate_vault(&vault) {
+            Ok(()) => break absolute_vault(&vault)?,
+            Err(problem) if vault.is_empty() => {
+                writeln!(output, "{}", problem).map_err(stdout_error)?
+            }
+            Err(problem) => {
+                let question = format!("{} Create it? [y/N]: ", problem);
+                if confirm(input, output, &question, false)? {
+                    let path = expand_tilde(&vault);
+                    fs::create_dir_all(&path).map_err(|e| Error::io(&path, e))?;
+                    break absolute_vault(&vault)?;
+                }
+            }
+        }
+    };
+
+    let file_path_format = ask_valid(
+        input,
+        output,
+        "Daily note path in the vault",
+        &defaults.file_path_format,
+        validate_file_path_format,
+    )?;
+    let section_header = ask_valid(
+        input,
+        output,
+        "Section header for log entries",
+        &defaults.section_header,
+        validate_section_header,
+    )?;
+    let list_type = ask_valid(
+        input,
+        output,
+        "List type (bullet or table)",
+        &defaults.list_type.to_string(),
+        // A custom list type needs an entry format, which is set in the file
+        |answer| match answer.parse::<ListType>() {
+            Ok(ListType::Bullet | ListType::Table) => Ok(()),
+            _ => Err("Expected 'bullet' or 'table'.".to_string()),
+        },
+    )?
+    .parse()
+    .unwrap_or(ListType::Bullet);
+    let locale = ask_valid(
+        input,
+        output,
+        "Locale for weekday and month names, e.g. en_US or nb-NO (empty for English)",
+        "",
+        validate_locale,
+    )?;
+    let template_path = ask(
+        input,
+        output,
+        "Template for new notes (empty for none)",
+        None,
+    )?;
+
+    let create_template = !template_path.is_empty()
+        && !expand_tilde(&template_path).exists()
+        && confirm(
+            input,
+            output,
+            &format!(
+                "{} does not exist. Create a starter template? [Y/n]: ",
+                template_path
+            ),
+            true,
+        )?;
+
+    Ok(InitSettings {
+        vault,
+        file_path_format,
+        section_header,
+        list_type,
+        locale: Some(locale).filter(|l| !l.is_empty()),
+        template_path: Some(template_path).filter(|t| !t.is_empty()),
+        create_template,
+    })
+}
+
+/// Write the configuration file and, if requested, the starter template
+pub fn write_init(config_path: &Path, settings: &InitSettings) -> Result<()> {
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+    }
+    fs::write(config_path, render_config(settings)).map_err(|e| Error::io(config_path, e))?;
+
+    if let (true, Some(template_path)) = (settings.create_template, &settings.template_path) {
+        let path = expand_tilde(template_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+        }
+        fs::write(&path, render_template(settings)).map_err(|e| Error::io(&path, e))?;
+    }
+    Ok(())
+}
+
+/// The configuration file for `settings`, with comments describing each setting
+pub fn render_config(settings: &InitSettings) -> String {
+    let optional = |key: &str, value: &Option<String>, example: &str| match value {
+        Some(value) => format!("{}: {}\n", key, quote(value)),
+        None => format!("# {}: {}\n", key, quote(example)),
+    };
+
+    format!(
+        "# obsidian-logging configuration, written by `obsidian-logging --init`.
+# See obsidian-logging.example.yaml for all settings.
+
+# Directory of the Obsidian vault
+vault: {vault}
+
+# Path of the daily note inside the vault. Tokens such as {{year}}, {{month}},
+# {{date}}, {{week}}, {{weekday}} and {{date:%Y/W%V}} are replaced with the date
+# of the note.
+file_path_format: {file_path_format}
+
+# Heading of the section that log entries are added to
+section_header: {section_header}
+# Sections for `-c <category>` are added with section_header_<category>:
+# section_header_work: \"## Work\"
+
+# How entries are written: bullet or table
+list_type: {list_type}
+
+# 12 or 24 hour timestamps
+time_format: 24
+
+# Locale for weekday names in templates
+{locale}
+# Template for new daily notes. Supports {{today}}, {{yesterday}},
+# {{tomorrow}}, {{weekday}}, {{created}}, {{date+7d:%Y-%m-%d}} and
+# {{if weekend}}...{{end}}, see the README for all placeholders.
+{template_path}
+# Shorthands for common entries, used with -p <name>
+# phrases:
+#   lunch: \"Lunch break\"
+",
+        vault = quote(&settings.vault),
+        file_path_format = quote(&settings.file_path_format),
+        section_header = quote(&settings.section_header),
+        list_type = settings.list_type,
+        locale = optional("locale", &settings.locale, "en_US"),
+        template_path = optional(
+            "template_path",
+            &settings.template_path,
+            "~/.config/obsidian-logging/template.md"
+        ),
+    )
+}
+
+/// A starter template with links to the neighbouring notes and the log section
+pub fn render_template(settings: &InitSettings) -> String {
+    format!(
+        "# {{today}}, {{weekday}}\n\n[[{{yesterday}}]] | [[{{tomorrow}}]]\n\n{}\n\n",
+        settings.section_header
+    )
+}
+
+fn validate_vault(vault: &str) -> std::result::Result<(), String> {
+    if vault.is_empty() {
+        return Err("The vault directory is required.".to_string());
+    }
+    if expand_tilde(vault).is_dir() {
+        Ok(())
+    } else {
+        Err(format!("Directory '{}' does not exist.", vault))
+    }
+}
+
+/// The vault as an absolute path. A relative vault in the config file is
+/// resolved against the file's directory, not the directory init ran in.
+fn absolute_vault(vault: &str) -> Result<String> {
+    let path = expand_tilde(vault);
+    let path = fs::canonicalize(&path).map_err(|e| Error::io(&path, e))?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+fn validate_file_path_format(format: &str) -> std::result::Result<(), String> {
+    let config = Config {
+        file_path_format: format.to_string(),
+        ..Config::default()
+    };
+    if utils::validate_file_path_format(&config, Period::Daily).is_err() {
+        Err("The path must identify the day, e.g. with {date} or {year}/{month}/{day}.".to_string())
+    } else if !format.ends_with(".md") {
+        Err("The path must end with .md.".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn validate_section_header(header: &str) -> std::result::Result<(), String> {
+    let text = header.trim_start_matches('#');
+    if text.len() == header.len() || !text.starts_with(' ') || text.trim().is_empty() {
+        Err("The section header must be a markdown heading, e.g. '## Log'.".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn validate_locale(locale: &str) -> std::result::Result<(), String> {
+    if locale.is_empty() || parse_locale(locale).is_some() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown locale '{}'. Use a name like en_US, nb-NO or de.",
+            locale
+        ))
+    }
+}
+
+/// Ask until the answer passes `validate`, printing the problem otherwise
+fn ask_valid<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    question: &str,
+    default: &str,
+    validate: impl Fn(&str) -> std::result::Result<(), String>,
+) -> Result<String> {
+    loop {
+        let answer = ask(
+            input,
+            output,
+            question,
+            Some(default).filter(|d| !d.is_empty()),
+        )?;
+        match validate(&answer) {
+            Ok(()) => return Ok(answer),
+            Err(problem) => writeln!(output, "{}", problem).map_err(stdout_error)?,
+        }
+    }
+}
+
+/// Ask one question, returning the trimmed answer or `default` for an empty one
+fn ask<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    question: &str,
+    default: Option<&str>,
+) -> Result<String> {
+    match default.filter(|d| !d.is_empty()) {
+        Some(default) => write!(output, "{} [{}]: ", question, default),
+        None => write!(output, "{}: ", question),
+    }
+    .and_then(|_| output.flush())
+    .map_err(stdout_error)?;
+
+    let answer = read_answer(input)?;
+    Ok(if answer.is_empty() {
+        default.unwrap_or_default().to_string()
+    } else {
+        answer
+    })
+}
+
+fn confirm<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    question: &str,
+    default: bool,
+) -> Result<bool> {
+    loop {
+        write!(output, "{}", question)
+            .and_then(|_| output.flush())
+            .map_err(stdout_error)?;
+        match read_answer(input)?.to_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => writeln!(output, "Please answer y or n.").map_err(stdout_error)?,
+        }
+    }
+}
+
+/// Read one line of input. The end of input cancels the setup.
+fn read_answer<R: BufRead>(input: &mut R) -> Result<String> {
+    let mut line = String::new();
+    let read = input
+        .read_line(&mut line)
+        .map_err(|e| Error::io("<stdin>", e))?;
+    if read == 0 {
+        return Err(Error::io(
+            "<stdin>",
+            io::Error::new(io::ErrorKind::UnexpectedEof, "setup cancelled"),
+        ));
+    }
+    Ok(line.trim().to_string())
+}
+
+fn stdout_error(e: io::Error) -> Error {
+    Error::io("<stdout>", e)
+}
+
+/// Quote a value as a YAML double-quoted string
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -1,7 +1,47 @@
-use crate::config::Config;
-use crate::utils::{extract_log_entries, get_log_path_for_date};
-use chrono::{Duration, Local};
-use std::fs::read_to_string;
+use crate::config::{Config, Period};
+use crate::document::{DailyLog, LogEntry, Section, TaskStatus};
+use crate::error::{Error, Result};
+use crate::i18n::Messages;
+use crate::output::{render_records, EntryRecord, OutputFormat};
+use crate::utils::get_log_path_for_category;
+use chrono::{Duration, Local, NaiveDate};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// A section selected for listing, with the date of the note it was found in
+type SelectedSection<'a, 'b> = (NaiveDate, &'a str, &'b Section);
+
+/// Which tasks to list. Entries that are not tasks are left out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskFilter {
+    Open,
+    Done,
+    All,
+}
+
+impl TaskFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        match (self, entry.task) {
+            (_, None) => false,
+            (TaskFilter::All, Some(_)) => true,
+            (TaskFilter::Open, Some(status)) => status == TaskStatus::Open,
+            (TaskFilter::Done, Some(status)) => status == TaskStatus::Done,
+        }
+    }
+}
+
+impl FromStr for TaskFilter {
+    type Err = ();
+
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "open" => Ok(TaskFilter::Open),
+            "done" => Ok(TaskFilter::Done),
+            "all" => Ok(TaskFilter::All),
+            _ => Err(()),
+        }
+    }
+}
 
 pub fn list_log_for_day(
     relative_day: i64,
@@ -9,141 +49,290 @@ pub fn list_log_for_day(
     silent: bool,
     include_header: bool,
     categories: &[String],
-) {
+    format: OutputFormat,
+    tasks: Option<TaskFilter>,
+) -> Result<()> {
     let date = Local::now().date_naive() - Duration::days(relative_day);
-    let log_path = get_log_path_for_date(date, config);
+    let headers = selected_headers(config, categories);
+    // Weekly, monthly and yearly notes hold the entries of other days too
+    let notes = load_notes(date, config, &headers, |_, entry| {
+        entry.date.is_none_or(|entry_date| entry_date == date)
+            && tasks.is_none_or(|filter| filter.matches(entry))
+    })?;
+    let sections = selected_sections(&notes, date, config, &headers);
+    let messages = config.messages()?;
 
-    if !log_path.exists() {
+    if format != OutputFormat::Markdown {
         if !silent {
-            println!("No log found for {}", date);
+            print!("{}", render_records(&records_for_day(&sections), format));
         }
-        return;
+        return Ok(());
     }
 
-    let content = read_to_string(&log_path).unwrap_or_else(|_| {
+    if notes.is_empty() {
         if !silent {
-            println!("Error reading log file");
+            println!("{}", messages.format("no_log_found", &[("date", &date)]));
         }
-        String::new()
-    });
+        return Ok(());
+    }
 
-    // Handle different category scenarios
-    if categories.is_empty() {
-        // No categories specified - list default section only
-        let section_header = config.get_section_header_for_category(None);
-        let (_, _, entries, _) = extract_log_entries(
-            &content,
-            section_header,
-            &config.list_type,
-            config,
-            include_header,
-        );
+    if silent {
+        return Ok(());
+    }
 
-        if entries.is_empty() {
-            if !silent {
-                println!("No entries found for {}", date);
-            }
-            return;
-        }
+    if sections.is_empty() && categories.is_empty() {
+        println!(
+            "{}",
+            messages.format("no_entries_for_day", &[("date", &date)])
+        );
+        return Ok(());
+    }
 
-        if !silent {
-            println!("Log entries for {}:", date);
-            for entry in entries {
-                println!("{}", entry);
-            }
-        }
-    } else if categories.len() == 1 && categories[0] == "all" {
-        // Special case: list all categories
-        list_all_categories(&content, config, silent, include_header, date);
-    } else {
-        // List specific categories
-        list_specific_categories(&content, config, silent, include_header, date, categories);
+    print_day(
+        date,
+        &sections,
+        config,
+        &messages,
+        include_header,
+        categories,
+    );
+    if sections.is_empty() && !is_all(categories) {
+        println!("{}", messages.get("no_entries_for_categories"));
     }
+
+    Ok(())
 }
 
-fn list_all_categories(
-    content: &str,
+/// List entries for every day from `from` to `to` (inclusive), grouped by date.
+/// Days without a note or without matching entries are skipped. Entries of
+/// weekly, monthly or yearly notes are listed on their own day, and those
+/// without a date on the first day of their period in the range. Structured
+/// formats emit a single document covering the whole range. With `tasks`, only
+/// the matching tasks are listed.
+#[allow(clippy::too_many_arguments)]
+pub fn list_log_for_range(
+    from: NaiveDate,
+    to: NaiveDate,
     config: &Config,
     silent: bool,
     include_header: bool,
-    date: chrono::NaiveDate,
-) {
-    if silent {
-        return;
+    categories: &[String],
+    format: OutputFormat,
+    tasks: Option<TaskFilter>,
+) -> Result<()> {
+    if from > to {
+        return Err(Error::InvalidEntry(format!(
+            "start date {} is after end date {}",
+            from, to
+        )));
     }
 
-    println!("Log entries for {} (all categories):", date);
+    let headers = selected_headers(config, categories);
+    let messages = config.messages()?;
+    let mut found_any = false;
+    let mut records = Vec::new();
+    for date in from.iter_days().take_while(|d| *d <= to) {
+        let notes = load_notes(date, config, &headers, |category, entry| {
+            let on_this_day = match entry.date {
+                Some(entry_date) => entry_date == date,
+                None => date == from || config.period_for_category(category).start(date) == date,
+            };
+            on_this_day && tasks.is_none_or(|filter| filter.matches(entry))
+        })?;
+        let sections = selected_sections(&notes, date, config, &headers);
+        if sections.is_empty() {
+            continue;
+        }
 
-    // List default section first
-    let default_header = config.get_section_header_for_category(None);
-    let (_, _, default_entries, _) = extract_log_entries(
-        content,
-        default_header,
-        &config.list_type,
-        config,
-        include_header,
-    );
+        if format != OutputFormat::Markdown {
+            records.extend(records_for_day(&sections));
+        } else if !silent {
+            if found_any {
+                println!();
+            }
+            print_day(
+                date,
+                &sections,
+                config,
+                &messages,
+                include_header,
+                categories,
+            );
+        }
+        found_any = true;
+    }
 
-    if !default_entries.is_empty() {
-        println!("\n{}", default_header);
-        for entry in default_entries {
-            println!("{}", entry);
+    if format != OutputFormat::Markdown {
+        if !silent {
+            print!("{}", render_records(&records, format));
         }
+    } else if !found_any && !silent {
+        println!(
+            "{}",
+            messages.format("no_entries_in_range", &[("from", &from), ("to", &to)])
+        );
     }
 
-    // List all category sections
-    for (key, header) in &config.category_headers {
-        if key.starts_with("section_header_") {
-            let (_, _, entries, _) =
-                extract_log_entries(content, header, &config.list_type, config, include_header);
+    Ok(())
+}
+
+fn is_all(categories: &[String]) -> bool {
+    categories.len() == 1 && categories[0] == "all"
+}
 
-            if !entries.is_empty() {
-                println!("\n{}", header);
-                for entry in entries {
-                    println!("{}", entry);
+/// Categories and section headers selected by `categories`
+fn selected_headers<'a>(
+    config: &'a Config,
+    categories: &'a [String],
+) -> Vec<(Option<&'a str>, &'a str)> {
+    if categories.is_empty() {
+        // No categories specified - list default section only
+        vec![(None, config.get_section_header_for_category(None))]
+    } else if is_all(categories) {
+        // Special case: default section first, then all category sections by name
+        let mut category_headers: Vec<(&str, &str)> = config
+            .category_headers
+            .iter()
+            .filter_map(|(key, header)| {
+                key.strip_prefix("section_header_")
+                    .map(|category| (category, header.as_str()))
+            })
+            .collect();
+        category_headers.sort();
+        let mut headers = vec![(None, config.get_section_header_for_category(None))];
+        headers.extend(
+            category_headers
+                .into_iter()
+                .map(|(category, header)| (Some(category), header)),
+        );
+        headers
+    } else {
+        categories
+            .iter()
+            .map(|category| {
+                (
+                    Some(category.as_str()),
+                    config.get_section_header_for_category(Some(category)),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Load the notes that the selected categories are logged to for `date`: the
+/// daily note and any weekly, monthly or yearly notes. Each note is loaded
+/// once, and notes that do not exist are left out. The selected sections only
+/// keep the entries for which `keep` returns true, given their category.
+fn load_notes(
+    date: NaiveDate,
+    config: &Config,
+    headers: &[(Option<&str>, &str)],
+    keep: impl Fn(Option<&str>, &LogEntry) -> bool,
+) -> Result<Vec<(PathBuf, DailyLog)>> {
+    let mut notes: Vec<(PathBuf, DailyLog)> = Vec::new();
+    for (category, _) in headers {
+        let path = get_log_path_for_category(date, config, *category)?;
+        if notes.iter().any(|(loaded, _)| *loaded == path) {
+            continue;
+        }
+        if let Some(mut log) = DailyLog::load(&path, config)? {
+            for (category, header) in headers {
+                if let Some(section) = log.section_mut(header) {
+                    section.entries.retain(|entry| keep(*category, entry));
                 }
             }
+            notes.push((path, log));
         }
     }
+    Ok(notes)
 }
 
-fn list_specific_categories(
-    content: &str,
+/// The sections selected by `headers` in the loaded notes.
+/// Sections without entries are left out.
+fn selected_sections<'a, 'b>(
+    notes: &'b [(PathBuf, DailyLog)],
+    date: NaiveDate,
     config: &Config,
-    silent: bool,
+    headers: &[(Option<&str>, &'a str)],
+) -> Vec<SelectedSection<'a, 'b>> {
+    headers
+        .iter()
+        .filter_map(|(category, header)| {
+            let path = get_log_path_for_category(date, config, *category).ok()?;
+            let (_, log) = notes.iter().find(|(loaded, _)| *loaded == path)?;
+            let note_date = config.period_for_category(*category).start(date);
+            match log.section(header) {
+                Some(section) if !section.entries.is_empty() => Some((note_date, *header, section)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Flatten the selected sections into structured records, dated by the entry
+/// or else by their note
+fn records_for_day(sections: &[SelectedSection]) -> Vec<EntryRecord> {
+    sections
+        .iter()
+        .flat_map(|(date, header, section)| {
+            section.entries.iter().map(move |entry| EntryRecord {
+                date: entry.date.unwrap_or(*date),
+                time: entry.time,
+                category: section.category.clone(),
+                section: header.to_string(),
+                text: entry.text.clone(),
+                task: entry.task,
+            })
+        })
+        .collect()
+}
+
+fn print_day(
+    date: NaiveDate,
+    sections: &[SelectedSection],
+    config: &Config,
+    messages: &Messages,
     include_header: bool,
-    date: chrono::NaiveDate,
     categories: &[String],
 ) {
-    if silent {
+    if categories.is_empty() {
+        println!("{}", messages.format("entries_for_day", &[("date", &date)]));
+        for (_, _, section) in sections {
+            for entry in section.render_entries(&config.list_type, config, include_header) {
+                println!("{}", entry);
+            }
+        }
         return;
     }
 
-    let category_list = categories.join(", ");
-    println!("Log entries for {} (categories: {}):", date, category_list);
-
-    let mut found_any = false;
-
-    for category in categories {
-        let section_header = config.get_section_header_for_category(Some(category));
-        let (_, _, entries, _) = extract_log_entries(
-            content,
-            section_header,
-            &config.list_type,
-            config,
-            include_header,
+    if is_all(categories) {
+        println!(
+            "{}",
+            messages.format("entries_for_day_all_categories", &[("date", &date)])
+        );
+    } else {
+        println!(
+            "{}",
+            messages.format(
+                "entries_for_day_categories",
+                &[("date", &date), ("categories", &categories.join(", "))]
+            )
         );
-
-        if !entries.is_empty() {
-            found_any = true;
-            println!("\n{}", section_header);
-            for entry in entries {
-                println!("{}", entry);
-            }
-        }
     }
 
-    if !found_any {
-        println!("No entries found for the specified categories.");
+    for (note_date, header, section) in sections {
+        match config.period_for_category(section.category.as_deref()) {
+            Period::Daily => println!("\n{}", header),
+            period => println!(
+                "\n{}",
+                messages.format(
+                    &format!("{}_note", period),
+                    &[("header", header), ("date", note_date)]
+                )
+            ),
+        }
+        for entry in section.render_entries(&config.list_type, config, include_header) {
+            println!("{}", entry);
+        }
     }
 }
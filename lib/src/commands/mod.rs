@@ -1,3 +1,7 @@
 pub mod add;
+pub mod amend;
 pub mod edit;
+pub mod init;
 pub mod list;
+pub mod properties;
+pub mod search;
@@ -0,0 +1,94 @@
+use crate::commands::add::update_front_matter;
+use crate::config::Config;
+use crate::document::DailyLog;
+use crate::error::{Error, Result, Warning};
+use crate::storage::{write_atomic, VaultLock};
+use crate::utils::get_log_path_for_category;
+use chrono::NaiveDate;
+use serde_yaml::Value;
+use std::fs::create_dir_all;
+
+/// Parse a `key=value` property argument. `true`/`false`, numbers and lists
+/// such as `people=[Ann, Bo]` keep their types; any other value, including one
+/// with `#` or `: ` in it, is kept as it was written.
+pub fn parse_property(argument: &str) -> Result<(String, Value)> {
+    let (key, value) = argument.split_once('=').ok_or_else(|| {
+        Error::InvalidEntry(format!("property '{}' is not in KEY=VALUE form", argument))
+    })?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(Error::InvalidEntry(format!(
+            "property '{}' has no name",
+            argument
+        )));
+    }
+    let value = value.trim();
+    let value = match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        Some(items) if items.trim().is_empty() => Value::Sequence(Vec::new()),
+        Some(items) => Value::Sequence(items.split(',').map(parse_scalar).collect()),
+        None => parse_scalar(value),
+    };
+    Ok((key.to_string(), value))
+}
+
+fn parse_scalar(value: &str) -> Value {
+    let value = value.trim();
+    match value {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => {
+            if let Ok(number) = value.parse::<i64>() {
+                Value::Number(number.into())
+            } else if let Some(number) = value.parse::<f64>().ok().filter(|n| n.is_finite()) {
+                Value::Number(number.into())
+            } else {
+                Value::String(value.to_string())
+            }
+        }
+    }
+}
+
+/// Set front matter properties of the note that `category` is logged to for `date`,
+/// creating the note from the template if it does not exist yet. Returns the
+/// warnings about the template and the front matter.
+pub fn set_properties(
+    date: NaiveDate,
+    properties: &[(String, Value)],
+    config: &Config,
+    silent: bool,
+    category: Option<&str>,
+) -> Result<Vec<Warning>> {
+    let file_path = get_log_path_for_category(date, config, category)?;
+    let _lock = VaultLock::acquire(config)?;
+    if let Some(parent) = file_path.parent() {
+        create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+    }
+    let (mut log, mut warnings) = match DailyLog::load(&file_path, config)? {
+        Some(log) => (log, Vec::new()),
+        None => DailyLog::from_template_for_category(config, category, date)?,
+    };
+
+    for (key, value) in properties {
+        log.set_property(key, value.clone())
+            .map_err(|message| Error::FrontMatter {
+                path: file_path.clone(),
+                message,
+            })?;
+    }
+    warnings.extend(update_front_matter(&mut log, &file_path, config));
+
+    let new_content = log.render(config);
+    write_atomic(&file_path, &(new_content.trim_end().to_string() + "\n"))?;
+
+    if !silent {
+        let keys: Vec<&str> = properties.iter().map(|(key, _)| key.as_str()).collect();
+        println!(
+            "{}",
+            config
+                .messages()?
+                .format("properties_set", &[("properties", &keys.join(", "))])
+        );
+    }
+
+    Ok(warnings)
+}
@@ -0,0 +1,123 @@
+use crate::config::Config;
+use crate::document::{DailyLog, LogEntry};
+use crate::error::{Error, Result, Warning};
+use crate::utils::{find_log_files, format_time};
+use chrono::NaiveDate;
+use regex::{Regex, RegexBuilder};
+
+/// How the search pattern is matched against entry text
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Match without regard to case
+    pub ignore_case: bool,
+    /// Treat the pattern as a regular expression instead of a plain substring
+    pub regex: bool,
+    /// Stop after this many matches
+    pub limit: Option<usize>,
+    /// List the oldest matches first instead of the newest
+    pub oldest_first: bool,
+    /// Only search these categories; empty or `all` searches every log section
+    pub categories: Vec<String>,
+}
+
+/// An entry that matched a search, with its date: that of the entry in weekly,
+/// monthly and yearly notes, and otherwise that of the note it was found in
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchMatch {
+    pub date: NaiveDate,
+    pub entry: LogEntry,
+}
+
+/// Search the log sections of all daily and periodic notes in the vault.
+/// Matches are ordered newest first unless `oldest_first` is set. Folders that
+/// could not be searched are returned as warnings.
+pub fn search_entries(
+    pattern: &str,
+    options: &SearchOptions,
+    config: &Config,
+) -> Result<(Vec<SearchMatch>, Vec<Warning>)> {
+    let source = if options.regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    let matcher: Regex = RegexBuilder::new(&source)
+        .case_insensitive(options.ignore_case)
+        .build()
+        .map_err(|e| Error::InvalidPattern {
+            pattern: pattern.to_string(),
+            message: e.to_string(),
+        })?;
+
+    let all_categories =
+        options.categories.is_empty() || options.categories.iter().any(|c| c == "all");
+
+    let mut matches = Vec::new();
+    let (files, warnings) = find_log_files(config)?;
+    for (date, path) in files {
+        let Some(log) = DailyLog::load(&path, config)? else {
+            continue;
+        };
+
+        matches.extend(
+            log.entries()
+                .filter(|entry| {
+                    all_categories
+                        || options
+                            .categories
+                            .iter()
+                            .any(|c| entry.category.as_deref() == Some(c.as_str()))
+                })
+                .filter(|entry| matcher.is_match(&entry.text))
+                .map(|entry| SearchMatch {
+                    date: entry.date.unwrap_or(date),
+                    entry: entry.clone(),
+                }),
+        );
+    }
+
+    // The entries of a periodic note fall between those of the daily notes
+    matches.sort_by_key(|m| (m.date, m.entry.time));
+    if !options.oldest_first {
+        matches.reverse();
+    }
+    if let Some(limit) = options.limit {
+        matches.truncate(limit);
+    }
+
+    Ok((matches, warnings))
+}
+
+/// Search all daily notes and print the matching entries with date, time and
+/// category. Returns the warnings of [`search_entries`].
+pub fn search_logs(
+    pattern: &str,
+    options: &SearchOptions,
+    config: &Config,
+    silent: bool,
+) -> Result<Vec<Warning>> {
+    let (matches, warnings) = search_entries(pattern, options, config)?;
+
+    if silent {
+        return Ok(warnings);
+    }
+
+    if matches.is_empty() {
+        let messages = config.messages()?;
+        println!(
+            "{}",
+            messages.format("no_search_matches", &[("pattern", &pattern)])
+        );
+        return Ok(warnings);
+    }
+
+    for m in matches {
+        let time = format_time(m.entry.time, &config.time_format);
+        match &m.entry.category {
+            Some(category) => println!("{} {} [{}] {}", m.date, time, category, m.entry.text),
+            None => println!("{} {} {}", m.date, time, m.entry.text),
+        }
+    }
+
+    Ok(warnings)
+}
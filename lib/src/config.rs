@@ -1,13 +1,36 @@
+use crate::entry_format::EntryFormat;
+use crate::error::Error;
+use crate::i18n::Messages;
+use crate::locale;
+use crate::utils::{expand_tilde, validate_file_path_format};
+use chrono::{Datelike, Duration, Locale, NaiveDate};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ListType {
     Bullet,
     Table,
+    /// The line format configured as `entry_format`
+    Custom,
+}
+
+impl Serialize for ListType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            ListType::Bullet => serializer.serialize_str("Bullet"),
+            ListType::Table => serializer.serialize_str("Table"),
+            ListType::Custom => serializer.serialize_str("Custom"),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -33,15 +56,33 @@ impl<'de> Deserialize<'de> for ListType {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        match s.to_lowercase().as_str() {
-            "bullet" => Ok(ListType::Bullet),
-            "table" => Ok(ListType::Table),
-            _ => Err(serde::de::Error::custom(format!(
-                "Invalid list type '{}'. Expected 'bullet' or 'table' (case insensitive)",
-                s
-            ))),
+        use serde::de::Visitor;
+        use std::fmt;
+
+        struct ListTypeVisitor;
+
+        impl<'de> Visitor<'de> for ListTypeVisitor {
+            type Value = ListType;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("'bullet', 'table' or 'custom'")
+            }
+
+            // Errors are raised inside the visitor so that they carry the position in the file
+            fn visit_str<E>(self, value: &str) -> Result<ListType, E>
+            where
+                E: serde::de::Error,
+            {
+                value.parse().map_err(|_| {
+                    E::custom(format!(
+                        "Invalid list type '{}'. Expected 'bullet', 'table' or 'custom' (case insensitive)",
+                        value
+                    ))
+                })
+            }
         }
+
+        deserializer.deserialize_str(ListTypeVisitor)
     }
 }
 
@@ -116,6 +157,7 @@ impl FromStr for ListType {
         match input.to_lowercase().as_str() {
             "bullet" => Ok(ListType::Bullet),
             "table" => Ok(ListType::Table),
+            "custom" => Ok(ListType::Custom),
             _ => Err(()),
         }
     }
@@ -138,10 +180,136 @@ impl std::fmt::Display for ListType {
         match self {
             ListType::Bullet => write!(f, "bullet"),
             ListType::Table => write!(f, "table"),
+            ListType::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+/// How often a new note is started
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum Period {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Period {
+    /// The first day of the period containing `date`. Weeks start on Monday.
+    pub fn start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Daily => date,
+            Period::Weekly => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Period::Monthly => date.with_day(1).unwrap_or(date),
+            Period::Yearly => date.with_ordinal(1).unwrap_or(date),
+        }
+    }
+}
+
+impl FromStr for Period {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "daily" => Ok(Period::Daily),
+            "weekly" => Ok(Period::Weekly),
+            "monthly" => Ok(Period::Monthly),
+            "yearly" => Ok(Period::Yearly),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Period::Daily => write!(f, "daily"),
+            Period::Weekly => write!(f, "weekly"),
+            Period::Monthly => write!(f, "monthly"),
+            Period::Yearly => write!(f, "yearly"),
+        }
+    }
+}
+
+impl Serialize for Period {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Period {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Visitor;
+        use std::fmt;
+
+        struct PeriodVisitor;
+
+        impl<'de> Visitor<'de> for PeriodVisitor {
+            type Value = Period;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("'weekly', 'monthly' or 'yearly'")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Period, E>
+            where
+                E: serde::de::Error,
+            {
+                match value.parse() {
+                    Ok(Period::Daily) => Err(E::custom(
+                        "daily notes are configured with file_path_format and template_path",
+                    )),
+                    Ok(period) => Ok(period),
+                    Err(()) => Err(E::custom(format!(
+                        "Invalid period '{}'. Expected 'weekly', 'monthly' or 'yearly'",
+                        value
+                    ))),
+                }
+            }
         }
+
+        deserializer.deserialize_str(PeriodVisitor)
     }
 }
 
+/// A weekly, monthly or yearly note that some categories are logged to
+/// instead of the daily note
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PeriodicNote {
+    /// Path of the note inside the vault, with the same tokens as `file_path_format`
+    pub file_path_format: String,
+    /// Template for new notes. Without one, new notes start out empty.
+    #[serde(default)]
+    pub template_path: Option<String>,
+    /// Categories whose entries go to this note
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+/// Front matter properties kept up to date when entries are added or changed.
+/// Each setting names the property, and properties without a name are left alone.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FrontMatterConfig {
+    /// Set to the time of the last change
+    #[serde(default)]
+    pub updated: Option<String>,
+    /// Set to the number of entries in the default section, with `_<category>`
+    /// appended for the sections of categories
+    #[serde(default)]
+    pub entries: Option<String>,
+    /// Extended with the `#tags` used in entries
+    #[serde(default)]
+    pub tags: Option<String>,
+}
+
 impl std::fmt::Display for TimeFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -157,45 +325,79 @@ pub struct Config {
     pub file_path_format: String,
     pub section_header: String,
     pub list_type: ListType,
+    /// The line format of `list_type: custom`, such as `- **{time}** — {text}`.
+    /// Lines in this format are recognised whatever the list type.
+    pub entry_format: Option<EntryFormat>,
     pub template_path: Option<String>,
     pub locale: Option<String>,
     pub time_format: TimeFormat,
     pub time_label: String,
     pub event_label: String,
+    /// Labels of table columns after the time and the entry, such as `End`,
+    /// `Duration` or `Tags`
+    pub table_columns: Vec<String>,
     pub category_headers: std::collections::HashMap<String, String>,
+    /// Regular expressions matching the header of a log section besides the
+    /// configured header itself, by category or `default` for `section_header`
+    pub section_patterns: std::collections::HashMap<String, String>,
+    /// A line that ends log sections before the next heading, such as `---`
+    pub section_end: Option<String>,
     pub phrases: std::collections::HashMap<String, String>,
+    /// The profile these settings were taken from, if any
+    #[serde(skip)]
+    pub profile: Option<String>,
+    pub periodic_notes: std::collections::HashMap<Period, PeriodicNote>,
+    /// Variables available to templates as `{name}`
+    pub template_variables: std::collections::HashMap<String, String>,
+    pub front_matter: FrontMatterConfig,
+    /// Directory with translation overrides, `<config dir>/translations` if not set
+    pub translations_dir: Option<String>,
 }
 
 fn default_time_format() -> TimeFormat {
     TimeFormat::Hour24
 }
 
-fn default_time_label() -> String {
-    "Tidspunkt".to_string()
-}
-
-fn default_event_label() -> String {
-    "Hendelse".to_string()
+/// The built-in translation of `key` for a configured locale tag
+fn default_label(locale: Option<&str>, key: &str) -> String {
+    Messages::builtin(locale::resolve_locale(locale))
+        .get(key)
+        .to_string()
 }
 
 impl Config {
     /// Get the conjunction word based on the configured locale
-    pub fn get_conjunction(&self) -> &'static str {
-        match self.locale.as_deref() {
-            Some("no") | Some("nb") | Some("nn") => "og",
-            Some("da") => "og",
-            Some("sv") => "och",
-            Some("de") => "und",
-            Some("fr") => "et",
-            Some("es") => "y",
-            Some("it") => "e",
-            Some("pt") => "e",
-            Some("ru") => "и",
-            Some("ja") => "と",
-            Some("ko") => "와",
-            Some("zh") => "和",
-            _ => "and", // Default to English
-        }
+    pub fn get_conjunction(&self) -> String {
+        self.messages()
+            .unwrap_or_else(|_| Messages::builtin(self.resolved_locale()))
+            .get("conjunction")
+            .to_string()
+    }
+
+    /// The labels and messages for the configured locale, with the overrides from
+    /// the translations directory. Fails if an override file cannot be read.
+    pub fn messages(&self) -> Result<Messages, Error> {
+        let dir = match &self.translations_dir {
+            Some(dir) => Some(expand_tilde(dir)),
+            None => get_config_dir().map(|dir| dir.join("translations")),
+        };
+        Messages::load(self.resolved_locale(), dir.as_deref())
+    }
+
+    /// Whether `time_label` or `event_label` differ from the translations of the
+    /// locale. Labels that are not configured are taken from the translations.
+    pub fn labels_configured(&self) -> bool {
+        let messages = self
+            .messages()
+            .unwrap_or_else(|_| Messages::builtin(self.resolved_locale()));
+        self.time_label != messages.get("time_label")
+            || self.event_label != messages.get("event_label")
+    }
+
+    /// The configured locale, falling back to a locale of the same language and
+    /// then to English
+    pub fn resolved_locale(&self) -> Locale {
+        locale::resolve_locale(self.locale.as_deref())
     }
 }
 
@@ -229,8 +431,16 @@ impl<'de> Deserialize<'de> for Config {
                 let mut time_format = None;
                 let mut time_label = None;
                 let mut event_label = None;
+                let mut table_columns = None;
                 let mut category_headers = std::collections::HashMap::new();
+                let mut section_patterns = std::collections::HashMap::new();
+                let mut section_end = None;
                 let mut phrases = std::collections::HashMap::new();
+                let mut periodic_notes = std::collections::HashMap::new();
+                let mut template_variables = std::collections::HashMap::new();
+                let mut front_matter = FrontMatterConfig::default();
+                let mut translations_dir = None;
+                let mut entry_format = None;
 
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
@@ -288,11 +498,66 @@ impl<'de> Deserialize<'de> for Config {
                             }
                             event_label = Some(map.next_value()?);
                         }
+                        "table_columns" => {
+                            if table_columns.is_some() {
+                                return Err(de::Error::duplicate_field("table_columns"));
+                            }
+                            table_columns = Some(map.next_value()?);
+                        }
                         "phrases" => {
                             let phrases_map: std::collections::HashMap<String, String> =
                                 map.next_value()?;
                             phrases = phrases_map;
                         }
+                        "periodic_notes" => {
+                            periodic_notes = map.next_value()?;
+                        }
+                        "section_patterns" => {
+                            let patterns: std::collections::HashMap<String, String> =
+                                map.next_value()?;
+                            for (name, pattern) in &patterns {
+                                if let Err(e) = Regex::new(pattern) {
+                                    return Err(de::Error::custom(format!(
+                                        "invalid pattern for '{}': {}",
+                                        name, e
+                                    )));
+                                }
+                            }
+                            section_patterns = patterns;
+                        }
+                        "section_end" => {
+                            if section_end.is_some() {
+                                return Err(de::Error::duplicate_field("section_end"));
+                            }
+                            section_end = map.next_value::<Option<String>>()?;
+                        }
+                        "template_variables" => {
+                            template_variables = map.next_value()?;
+                        }
+                        "front_matter" => {
+                            front_matter = map.next_value()?;
+                        }
+                        "entry_format" => {
+                            if entry_format.is_some() {
+                                return Err(de::Error::duplicate_field("entry_format"));
+                            }
+                            entry_format = map.next_value::<Option<EntryFormat>>()?;
+                        }
+                        "translations_dir" => {
+                            if translations_dir.is_some() {
+                                return Err(de::Error::duplicate_field("translations_dir"));
+                            }
+                            translations_dir = Some(map.next_value()?);
+                        }
+                        "profiles" => {
+                            // Profiles are applied by `Config::load_profile`, parse them
+                            // here so that mistakes are reported with their position
+                            let _: std::collections::HashMap<String, Option<Config>> =
+                                map.next_value()?;
+                        }
+                        "default_profile" => {
+                            let _: String = map.next_value()?;
+                        }
                         _ => {
                             // Check if this is a category header (starts with "section_header_")
                             if key.starts_with("section_header_") {
@@ -306,6 +571,10 @@ impl<'de> Deserialize<'de> for Config {
                     }
                 }
 
+                let time_label =
+                    time_label.unwrap_or_else(|| default_label(locale.as_deref(), "time_label"));
+                let event_label =
+                    event_label.unwrap_or_else(|| default_label(locale.as_deref(), "event_label"));
                 Ok(Config {
                     vault: vault.unwrap_or_default(),
                     file_path_format: file_path_format.unwrap_or_else(|| {
@@ -320,10 +589,19 @@ impl<'de> Deserialize<'de> for Config {
                     template_path,
                     locale,
                     time_format: time_format.unwrap_or_else(default_time_format),
-                    time_label: time_label.unwrap_or_else(default_time_label),
-                    event_label: event_label.unwrap_or_else(default_event_label),
+                    time_label,
+                    event_label,
+                    table_columns: table_columns.unwrap_or_default(),
                     category_headers,
+                    section_patterns,
+                    section_end,
                     phrases,
+                    profile: None,
+                    periodic_notes,
+                    template_variables,
+                    front_matter,
+                    translations_dir,
+                    entry_format,
                 })
             }
         }
@@ -348,10 +626,19 @@ impl Default for Config {
             template_path: None,
             locale: None,
             time_format: TimeFormat::Hour24,
-            time_label: default_time_label(),
-            event_label: default_event_label(),
+            time_label: default_label(None, "time_label"),
+            event_label: default_label(None, "event_label"),
             category_headers: std::collections::HashMap::new(),
             phrases: std::collections::HashMap::new(),
+            profile: None,
+            periodic_notes: std::collections::HashMap::new(),
+            template_variables: std::collections::HashMap::new(),
+            front_matter: FrontMatterConfig::default(),
+            translations_dir: None,
+            entry_format: None,
+            table_columns: Vec::new(),
+            section_patterns: std::collections::HashMap::new(),
+            section_end: None,
         }
     }
 }
@@ -383,34 +670,570 @@ impl Config {
         }
     }
 
-    pub fn initialize() -> Config {
-        let config_dir = get_config_dir();
-        let config_path = config_dir.join("obsidian-logging.yaml");
+    /// Get the category whose section header is `header`, `None` for the default section
+    pub fn get_category_for_section_header(&self, header: &str) -> Option<&str> {
+        if header == self.section_header {
+            return None;
+        }
+        self.category_headers
+            .iter()
+            .find(|(_, h)| h.as_str() == header)
+            .and_then(|(key, _)| key.strip_prefix("section_header_"))
+    }
+
+    /// The weekly, monthly or yearly note that `category` is logged to, if any
+    pub fn periodic_note_for_category(
+        &self,
+        category: Option<&str>,
+    ) -> Option<(Period, &PeriodicNote)> {
+        let category = category?;
+        let mut notes: Vec<_> = self.periodic_notes.iter().collect();
+        // The shortest period wins if a category is listed more than once
+        notes.sort_by_key(|(period, _)| **period);
+        notes
+            .into_iter()
+            .find(|(_, note)| note.categories.iter().any(|c| c == category))
+            .map(|(period, note)| (*period, note))
+    }
+
+    /// How often a new note is started for `category`
+    pub fn period_for_category(&self, category: Option<&str>) -> Period {
+        self.periodic_note_for_category(category)
+            .map(|(period, _)| period)
+            .unwrap_or(Period::Daily)
+    }
+
+    /// The configuration for the note that `category` is logged to: for a
+    /// periodic note, `file_path_format` and `template_path` are taken from it
+    pub fn note_config(&self, category: Option<&str>) -> Cow<'_, Config> {
+        match self.periodic_note_for_category(category) {
+            Some((_, note)) => Cow::Owned(self.with_periodic_note(note)),
+            None => Cow::Borrowed(self),
+        }
+    }
+
+    /// The configurations of every kind of note: the daily note, followed by
+    /// the weekly, monthly and yearly notes in that order
+    pub fn note_configs(&self) -> Vec<Cow<'_, Config>> {
+        let mut periods: Vec<_> = self.periodic_notes.iter().collect();
+        periods.sort_by_key(|(period, _)| **period);
+        std::iter::once(Cow::Borrowed(self))
+            .chain(
+                periods
+                    .into_iter()
+                    .map(|(_, note)| Cow::Owned(self.with_periodic_note(note))),
+            )
+            .collect()
+    }
+
+    /// This configuration with the path and template of a periodic note
+    fn with_periodic_note(&self, note: &PeriodicNote) -> Config {
+        Config {
+            file_path_format: note.file_path_format.clone(),
+            template_path: note.template_path.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// The vault directory, with a leading `~` expanded
+    pub fn vault_path(&self) -> PathBuf {
+        expand_tilde(&self.vault)
+    }
+
+    /// Look up a predefined phrase by its shorthand
+    pub fn get_phrase(&self, key: &str) -> Result<&str, Error> {
+        self.phrases
+            .get(key)
+            .map(|s| s.as_str())
+            .ok_or_else(|| Error::PhraseNotFound(key.to_string()))
+    }
+
+    /// Read the configuration files found by [`config_files`] and apply the
+    /// `OBSIDIAN_VAULT_DIR` override, unless the selected profile sets the vault.
+    /// Without any file the defaults are used.
+    pub fn initialize() -> Result<Config, Error> {
+        Config::initialize_from(None, None)
+    }
+
+    /// Like [`Config::initialize`], but reads only `path` if given (the `--config` flag)
+    /// and uses the named `profile` (the `--profile` flag). Without a profile name,
+    /// `OBSIDIAN_LOGGING_PROFILE` or the configured `default_profile` is used.
+    pub fn initialize_from(path: Option<&Path>, profile: Option<&str>) -> Result<Config, Error> {
+        let profile = profile.map(str::to_string).or_else(profile_from_env);
+        Config::load_with_vault_override(&config_files(path), profile.as_deref())
+    }
+
+    /// Like [`Config::load_profile`], then let `OBSIDIAN_VAULT_DIR` override the
+    /// vault, unless the selected profile sets one
+    fn load_with_vault_override(paths: &[PathBuf], profile: Option<&str>) -> Result<Config, Error> {
+        let (mut config, profile_sets_vault) = Config::load_profile_settings(paths, profile)?;
+        if !profile_sets_vault {
+            if let Ok(vault_dir) = env::var("OBSIDIAN_VAULT_DIR") {
+                config.vault = vault_dir;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Read the configuration file at `path`, falling back to the defaults if it does not exist
+    pub fn load(path: &Path) -> Result<Config, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Config::from_yaml(&content, path),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(Error::io(path, e)),
+        }
+    }
+
+    /// Read several configuration files, each one overriding the keys set by the
+    /// ones before it. `phrases` are merged. A relative `vault` is resolved
+    /// against the directory of the file that sets it. The `default_profile`
+    /// is applied if one is configured.
+    pub fn load_layered(paths: &[PathBuf]) -> Result<Config, Error> {
+        Config::load_profile(paths, None)
+    }
+
+    /// Like [`Config::load_layered`], but applies the named profile on top of the
+    /// top-level settings. Fails if the profile is not defined.
+    pub fn load_profile(paths: &[PathBuf], profile: Option<&str>) -> Result<Config, Error> {
+        Config::load_profile_settings(paths, profile).map(|(config, _)| config)
+    }
+
+    /// [`Config::load_profile`], also telling whether the applied profile sets `vault`
+    fn load_profile_settings(
+        paths: &[PathBuf],
+        profile: Option<&str>,
+    ) -> Result<(Config, bool), Error> {
+        let Some(last) = paths.last() else {
+            return match profile {
+                Some(name) => Err(undefined_profile(name, None, &[])),
+                None => Ok((Config::default(), false)),
+            };
+        };
+
+        let mut merged = merge_files(paths)?;
+        let profiles = merged.remove("profiles");
+        let default_profile = merged.remove("default_profile");
+        let name = profile.map(str::to_string).or_else(|| {
+            default_profile
+                .as_ref()
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        });
+
+        let mut profile_sets_vault = false;
+        if let Some(name) = &name {
+            let profiles = match &profiles {
+                Some(serde_yaml::Value::Mapping(profiles)) => profiles.clone(),
+                _ => serde_yaml::Mapping::new(),
+            };
+            match profiles.get(name.as_str()) {
+                Some(serde_yaml::Value::Mapping(settings)) => {
+                    profile_sets_vault = settings.contains_key("vault");
+                    merge_layer(&mut merged, settings.clone())
+                }
+                Some(serde_yaml::Value::Null) => {}
+                _ => {
+                    return Err(undefined_profile(
+                        name,
+                        Some(last),
+                        &profile_names(&profiles),
+                    ))
+                }
+            }
+        }
+
+        let time_label_set = merged.contains_key("time_label");
+        let event_label_set = merged.contains_key("event_label");
+        let mut config: Config = serde_yaml::from_value(serde_yaml::Value::Mapping(merged))
+            .map_err(|e| Error::ConfigParse {
+                path: last.clone(),
+                line: None,
+                column: None,
+                message: e.to_string(),
+            })?;
+        config.profile = name;
+
+        // Labels that are not configured come from the translations, which may
+        // be overridden by the user
+        let messages = config.messages()?;
+        if !time_label_set {
+            config.time_label = messages.get("time_label").to_string();
+        }
+        if !event_label_set {
+            config.event_label = messages.get("event_label").to_string();
+        }
+        Ok((config, profile_sets_vault))
+    }
+
+    /// Parse configuration YAML. Errors report the line and column in `path`.
+    /// An empty file or one with only comments gives the defaults.
+    pub fn from_yaml(content: &str, path: &Path) -> Result<Config, Error> {
+        if matches!(serde_yaml::from_str(content), Ok(serde_yaml::Value::Null)) {
+            return Ok(Config::default());
+        }
+        serde_yaml::from_str(content).map_err(|e| {
+            let location = e.location();
+            let mut message = e.to_string();
+            // The position is reported separately
+            if let Some(index) = message.rfind(" at line ") {
+                message.truncate(index);
+            }
+            Error::ConfigParse {
+                path: path.to_path_buf(),
+                line: location.as_ref().map(|l| l.line()),
+                column: location.as_ref().map(|l| l.column()),
+                message,
+            }
+        })
+    }
+
+    /// Problems with the configured values: a vault that is not set or does not
+    /// exist, a `file_path_format` that does not give each day (or week, month
+    /// or year for periodic notes) its own note, a template file that does not
+    /// exist, a locale that is not known, and periodic note categories without a
+    /// section header
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = self.vault_problems();
+        if let Some(tag) = self.locale.as_deref() {
+            if locale::parse_locale(tag).is_none() {
+                problems.push(format!("locale '{}' is not known, English is used", tag));
+            }
+        }
+        if self.list_type == ListType::Custom && self.entry_format.is_none() {
+            problems.push("list_type is 'custom', but no entry_format is set".to_string());
+        }
+        problems.extend(validate_file_path_format(self, Period::Daily).err());
+        problems.extend(template_problem("template_path", &self.template_path));
+
+        let mut periods: Vec<_> = self.periodic_notes.keys().copied().collect();
+        periods.sort();
+        for period in periods {
+            let note = &self.periodic_notes[&period];
+            let config = Config {
+                file_path_format: note.file_path_format.clone(),
+                ..self.clone()
+            };
+            problems.extend(
+                validate_file_path_format(&config, period)
+                    .err()
+                    .map(|problem| format!("periodic_notes.{}: {}", period, problem)),
+            );
+            problems.extend(template_problem(
+                &format!("periodic_notes.{}.template_path", period),
+                &note.template_path,
+            ));
+            for category in &note.categories {
+                if !self
+                    .category_headers
+                    .contains_key(&format!("section_header_{}", category))
+                {
+                    problems.push(format!(
+                        "periodic_notes.{}: category '{}' has no section_header_{}",
+                        period, category, category
+                    ));
+                }
+                if self.period_for_category(Some(category)) != period {
+                    problems.push(format!(
+                        "periodic_notes.{}: category '{}' is already logged to the {} note",
+                        period,
+                        category,
+                        self.period_for_category(Some(category))
+                    ));
+                }
+            }
+        }
+        problems
+    }
+
+    fn vault_problems(&self) -> Vec<String> {
+        if self.vault.trim().is_empty() {
+            vec!["vault is not set (set 'vault' or OBSIDIAN_VAULT_DIR)".to_string()]
+        } else if !self.vault_path().is_dir() {
+            vec![format!("vault directory '{}' does not exist", self.vault)]
+        } else {
+            Vec::new()
+        }
+    }
 
-        // Try to read config file
-        let mut config: Config = if let Ok(config_str) = fs::read_to_string(&config_path) {
-            serde_yaml::from_str(&config_str).unwrap_or_default()
+    /// Fail unless the vault is set and exists, so that notes are never
+    /// written to an unexpected location
+    pub fn ensure_vault(&self) -> Result<(), Error> {
+        let problems = self.vault_problems();
+        if problems.is_empty() {
+            Ok(())
         } else {
-            Config::default()
+            Err(Error::ConfigInvalid {
+                path: None,
+                problems,
+            })
+        }
+    }
+}
+
+fn template_problem(key: &str, template_path: &Option<String>) -> Option<String> {
+    let template_path = template_path.as_ref()?;
+    (!expand_tilde(template_path).is_file())
+        .then(|| format!("{} '{}' does not exist", key, template_path))
+}
+
+/// Read and merge the configuration files, lowest precedence first
+fn merge_files(paths: &[PathBuf]) -> Result<serde_yaml::Mapping, Error> {
+    let mut merged = serde_yaml::Mapping::new();
+    for path in paths {
+        let content = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+        // Parse each file on its own first, so that errors point into that file
+        Config::from_yaml(&content, path)?;
+        let serde_yaml::Value::Mapping(mut layer) =
+            serde_yaml::from_str(&content).unwrap_or(serde_yaml::Value::Null)
+        else {
+            continue;
         };
 
-        // Override vault setting with environment variable if set
-        if let Ok(vault_dir) = env::var("OBSIDIAN_VAULT_DIR") {
-            config.vault = vault_dir;
+        let base = path.parent().unwrap_or(Path::new(""));
+        resolve_vault(&mut layer, base);
+        if let Some(serde_yaml::Value::Mapping(profiles)) = layer.get_mut("profiles") {
+            for (_, settings) in profiles.iter_mut() {
+                if let serde_yaml::Value::Mapping(settings) = settings {
+                    resolve_vault(settings, base);
+                }
+            }
         }
+        merge_layer(&mut merged, layer);
+    }
+    Ok(merged)
+}
 
-        config
+/// Resolve a relative `vault` against `base`, the directory of the file that sets it.
+/// An empty vault is left empty, so that it is reported as not set.
+fn resolve_vault(layer: &mut serde_yaml::Mapping, base: &Path) {
+    if let Some(serde_yaml::Value::String(vault)) = layer.get_mut("vault") {
+        if vault.trim().is_empty() {
+            return;
+        }
+        *vault = base
+            .join(expand_tilde(vault))
+            .to_string_lossy()
+            .into_owned();
+    }
+}
+
+/// Override the keys in `merged` with the ones set in `layer`.
+/// `phrases` are merged, and so are `profiles`, one whole profile at a time.
+fn merge_layer(merged: &mut serde_yaml::Mapping, layer: serde_yaml::Mapping) {
+    // Empty values (`template_path:`) leave the key unset
+    for (key, value) in layer.into_iter().filter(|(_, v)| !v.is_null()) {
+        match (merged.get_mut(&key), value) {
+            (Some(serde_yaml::Value::Mapping(existing)), serde_yaml::Value::Mapping(value))
+                if matches!(
+                    key.as_str(),
+                    Some("phrases" | "template_variables" | "profiles")
+                ) =>
+            {
+                existing.extend(value)
+            }
+            (_, value) => {
+                merged.insert(key, value);
+            }
+        }
     }
 }
 
-fn get_config_dir() -> PathBuf {
+fn profile_names(profiles: &serde_yaml::Mapping) -> Vec<String> {
+    profiles
+        .keys()
+        .filter_map(|k| k.as_str().map(String::from))
+        .collect()
+}
+
+fn undefined_profile(name: &str, path: Option<&PathBuf>, available: &[String]) -> Error {
+    let problem = if available.is_empty() {
+        format!(
+            "profile '{}' is not defined, no profiles are configured",
+            name
+        )
+    } else {
+        format!(
+            "profile '{}' is not defined (available: {})",
+            name,
+            available.join(", ")
+        )
+    };
+    Error::ConfigInvalid {
+        path: path.cloned(),
+        problems: vec![problem],
+    }
+}
+
+/// Environment variable selecting the profile to use
+pub const PROFILE_ENV_VAR: &str = "OBSIDIAN_LOGGING_PROFILE";
+
+fn profile_from_env() -> Option<String> {
+    env::var(PROFILE_ENV_VAR).ok().filter(|p| !p.is_empty())
+}
+
+/// Top-level keys of the configuration file, besides `section_header_<category>`
+const KNOWN_KEYS: &[&str] = &[
+    "vault",
+    "file_path_format",
+    "section_header",
+    "list_type",
+    "entry_format",
+    "template_path",
+    "locale",
+    "time_format",
+    "time_label",
+    "event_label",
+    "table_columns",
+    "section_patterns",
+    "section_end",
+    "phrases",
+    "periodic_notes",
+    "template_variables",
+    "front_matter",
+    "translations_dir",
+    "profiles",
+    "default_profile",
+];
+
+/// Keys that may not be set inside a profile
+const TOP_LEVEL_ONLY_KEYS: &[&str] = &["profiles", "default_profile"];
+
+/// Top-level keys in configuration YAML that are not understood, with their line numbers
+pub fn unknown_keys(content: &str) -> Vec<(usize, String)> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.starts_with([' ', '\t', '#', '-']))
+        .filter_map(|(i, line)| {
+            let key = line.split_once(':')?.0.trim().trim_matches(['"', '\'']);
+            let known = KNOWN_KEYS.contains(&key) || key.starts_with("section_header_");
+            (!known && !key.is_empty()).then(|| (i + 1, key.to_string()))
+        })
+        .collect()
+}
+
+/// Keys of the profiles in configuration YAML that are not understood, as
+/// (profile, key) pairs
+pub fn unknown_profile_keys(content: &str) -> Vec<(String, String)> {
+    let Ok(serde_yaml::Value::Mapping(config)) = serde_yaml::from_str(content) else {
+        return Vec::new();
+    };
+    let Some(serde_yaml::Value::Mapping(profiles)) = config.get("profiles") else {
+        return Vec::new();
+    };
+
+    let mut unknown = Vec::new();
+    for (name, settings) in profiles {
+        let (Some(name), serde_yaml::Value::Mapping(settings)) = (name.as_str(), settings) else {
+            continue;
+        };
+        for key in settings.keys().filter_map(|k| k.as_str()) {
+            let known = (KNOWN_KEYS.contains(&key) && !TOP_LEVEL_ONLY_KEYS.contains(&key))
+                || key.starts_with("section_header_");
+            if !known {
+                unknown.push((name.to_string(), key.to_string()));
+            }
+        }
+    }
+    unknown
+}
+
+/// Validate the configuration files: syntax and value errors, unknown keys,
+/// and the problems reported by [`Config::problems`] for the combined configuration
+/// with `profile` (or `OBSIDIAN_LOGGING_PROFILE`, or the default profile) applied.
+/// Returns the configuration if there are no problems.
+pub fn check_config_files(paths: &[PathBuf], profile: Option<&str>) -> Result<Config, Error> {
+    let mut problems = Vec::new();
+    for path in paths {
+        let content = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+        problems.extend(
+            unknown_keys(&content)
+                .into_iter()
+                .map(|(line, key)| format!("{}:{}: unknown key '{}'", path.display(), line, key)),
+        );
+        problems.extend(
+            unknown_profile_keys(&content)
+                .into_iter()
+                .map(|(name, key)| {
+                    format!(
+                        "{}: unknown key '{}' in profile '{}'",
+                        path.display(),
+                        key,
+                        name
+                    )
+                }),
+        );
+    }
+
+    let profile = profile.map(str::to_string).or_else(profile_from_env);
+    let config = Config::load_with_vault_override(paths, profile.as_deref())?;
+    problems.extend(config.problems());
+
+    if problems.is_empty() {
+        Ok(config)
+    } else {
+        Err(Error::ConfigInvalid {
+            path: paths.last().cloned(),
+            problems,
+        })
+    }
+}
+
+/// Environment variable naming the configuration file to use
+pub const CONFIG_ENV_VAR: &str = "OBSIDIAN_LOGGING_CONFIG";
+
+/// Name of the project-local configuration file
+pub const PROJECT_CONFIG_NAME: &str = ".obsidian-logging.yaml";
+
+/// The configuration files to read, lowest precedence first.
+///
+/// An explicit `path` or `OBSIDIAN_LOGGING_CONFIG` names the only file to read.
+/// Otherwise the user configuration (if it exists) is followed by the nearest
+/// `.obsidian-logging.yaml` in the current directory or one of its parents.
+pub fn config_files(path: Option<&Path>) -> Vec<PathBuf> {
+    if let Some(path) = path {
+        return vec![path.to_path_buf()];
+    }
+    if let Some(path) = env::var_os(CONFIG_ENV_VAR).filter(|p| !p.is_empty()) {
+        return vec![PathBuf::from(path)];
+    }
+
+    let mut files: Vec<PathBuf> = user_config_path()
+        .into_iter()
+        .filter(|p| p.is_file())
+        .collect();
+    files.extend(project_config_path());
+    files
+}
+
+/// Location of the user configuration file, `None` if no home or configuration
+/// directory is known
+pub fn user_config_path() -> Option<PathBuf> {
+    get_config_dir().map(|dir| dir.join("obsidian-logging.yaml"))
+}
+
+/// The nearest project-local configuration file, searching from the current directory upwards
+pub fn project_config_path() -> Option<PathBuf> {
+    let cwd = env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_NAME))
+        .find(|path| path.is_file())
+}
+
+fn get_config_dir() -> Option<PathBuf> {
+    let non_empty = |name: &str| {
+        env::var_os(name)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
     if cfg!(windows) {
         // On Windows, use %APPDATA%\obsidian-logging
-        let app_data = env::var("APPDATA").expect("APPDATA environment variable not set");
-        PathBuf::from(app_data).join("obsidian-logging")
+        non_empty("APPDATA").map(|app_data| app_data.join("obsidian-logging"))
+    } else if let Some(config_home) = non_empty("XDG_CONFIG_HOME") {
+        // $XDG_CONFIG_HOME/obsidian-logging
+        Some(config_home.join("obsidian-logging"))
     } else {
         // On Unix, use ~/.config/obsidian-logging
-        let home = env::var("HOME").expect("HOME environment variable not set");
-        PathBuf::from(home).join(".config").join("obsidian-logging")
+        non_empty("HOME").map(|home| home.join(".config").join("obsidian-logging"))
     }
 }
@@ -0,0 +1,1150 @@
+use crate::config::{Config, ListType};
+use crate::entry_format::EntryFormat;
+use crate::error::{Error, Result, Warning};
+use crate::front_matter::{collect_tags, FrontMatter};
+use crate::template::{get_template_content_for_category, get_template_content_for_date};
+use crate::utils::{format_duration, format_time, parse_time};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fs::read_to_string;
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+lazy_static! {
+    static ref BULLET_PATTERN: Regex =
+        Regex::new(r"^([-*+])\s+(?:\[([ xX])\]\s+)?((?:\d{4}-\d{2}-\d{2}\s+)?\d{1,2}:\d{2}(?::\d{2})?(?:\s*[AaPp][Mm])?)\s+(.+)$").unwrap();
+    /// The checkbox at the start of a task's table cell
+    static ref CHECKBOX_PATTERN: Regex = Regex::new(r"^\[([ xX])\]\s+").unwrap();
+    /// A cell of the line between a table header and its rows, like `---` or `:--:`
+    static ref TABLE_SEPARATOR_CELL: Regex = Regex::new(r"^:?-+:?$").unwrap();
+    static ref LINE_BREAK_TAG: Regex = Regex::new(r"(?i)<br\s*/?>").unwrap();
+}
+
+/// Indentation of the continuation lines of multi-line bullet entries
+const CONTINUATION_INDENT: &str = "  ";
+
+/// The bullet of new sections and of tables written as bullets. Sections that
+/// already use `-` or `+` keep their bullet.
+const DEFAULT_BULLET: char = '*';
+
+/// The bullet of tasks, `- [ ] 14:30:00 text`, whatever the bullet of the section
+const TASK_BULLET: char = '-';
+
+/// Whether a task entry has been ticked off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    Open,
+    Done,
+}
+
+impl TaskStatus {
+    pub(crate) fn from_checkbox(mark: &str) -> Self {
+        if mark.trim().is_empty() {
+            TaskStatus::Open
+        } else {
+            TaskStatus::Done
+        }
+    }
+
+    /// The markdown checkbox, `[ ]` or `[x]`
+    pub fn checkbox(&self) -> &'static str {
+        match self {
+            TaskStatus::Open => "[ ]",
+            TaskStatus::Done => "[x]",
+        }
+    }
+}
+
+/// A single timestamped entry in a log section
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// The day of an entry in a weekly, monthly or yearly note, written before
+    /// its time (`* 2026-10-15 21:05:00 text`). `None` in daily notes.
+    pub date: Option<NaiveDate>,
+    pub time: NaiveTime,
+    /// The entry text, without the checkbox of a task. Multi-line entries contain
+    /// `\n` between their lines.
+    pub text: String,
+    /// The status of a task (`- [ ] 14:30:00 text`), `None` for plain entries
+    pub task: Option<TaskStatus>,
+    /// Cells of the table columns after the time and the entry, as
+    /// `(column label, value)` in the order of the table
+    pub columns: Vec<(String, String)>,
+    /// Category of the section the entry belongs to, `None` for the default section
+    pub category: Option<String>,
+    /// The markdown lines the entry was parsed from, empty for entries not yet written
+    pub raw: String,
+}
+
+impl LogEntry {
+    /// Parse a bullet (`* 14:30:00 text`), task (`- [ ] 14:30:00 text`) or table
+    /// (`| 14:30:00 | text |`) line. The time may be preceded by a date. A task
+    /// in a table has its checkbox at the start of the text cell. `<br>` in a
+    /// table cell becomes a line break in the text. Further table cells are left
+    /// to the section, which knows their column labels.
+    pub fn parse(line: &str, category: Option<&str>) -> Option<LogEntry> {
+        let trimmed = line.trim();
+        let cells;
+        let (time, mut text, mut task) = if trimmed.starts_with('|') {
+            cells = table_cells(trimmed);
+            if cells.len() < 2 {
+                return None;
+            }
+            (
+                cells[0].as_str(),
+                LINE_BREAK_TAG.replace_all(&cells[1], "\n"),
+                None,
+            )
+        } else {
+            let captures = BULLET_PATTERN.captures(trimmed)?;
+            (
+                captures.get(3).unwrap().as_str().trim(),
+                Cow::Borrowed(captures.get(4).unwrap().as_str().trim()),
+                captures
+                    .get(2)
+                    .map(|mark| TaskStatus::from_checkbox(mark.as_str())),
+            )
+        };
+        if trimmed.starts_with('|') {
+            if let Some(captures) = CHECKBOX_PATTERN.captures(&text) {
+                task = Some(TaskStatus::from_checkbox(&captures[1]));
+                let start = captures.get(0).unwrap().end();
+                text = Cow::Owned(text[start..].to_string());
+            }
+        }
+
+        if text.is_empty() {
+            return None;
+        }
+
+        let (date, time) = parse_stamp(time)?;
+        Some(LogEntry {
+            date,
+            time,
+            text: text.into_owned(),
+            task,
+            columns: Vec::new(),
+            category: category.map(|c| c.to_string()),
+            raw: line.to_string(),
+        })
+    }
+
+    /// Parse a line written in a user-defined line format. A checkbox at the start
+    /// of the text is the task's if the format has no `{task}`.
+    pub fn parse_with_format(
+        line: &str,
+        category: Option<&str>,
+        format: &EntryFormat,
+    ) -> Option<LogEntry> {
+        let (time, mut text, mut task) = format.parse(line)?;
+        if !format.has_task() {
+            if let Some(captures) = CHECKBOX_PATTERN.captures(text) {
+                task = Some(TaskStatus::from_checkbox(&captures[1]));
+                text = &text[captures.get(0).unwrap().end()..];
+            }
+        }
+        if text.is_empty() {
+            return None;
+        }
+
+        let (date, time) = parse_stamp(time)?;
+        Some(LogEntry {
+            date,
+            time,
+            text: text.to_string(),
+            task,
+            columns: Vec::new(),
+            category: category.map(|c| c.to_string()),
+            raw: line.to_string(),
+        })
+    }
+
+    /// The cell of the table column with this label, ignoring case
+    pub fn column(&self, label: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|(l, _)| l.eq_ignore_ascii_case(label))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Append a continuation line of a multi-line bullet entry
+    fn push_continuation(&mut self, line: &str, blank_lines: usize) {
+        let content = line
+            .strip_prefix(CONTINUATION_INDENT)
+            .or_else(|| line.strip_prefix('\t'))
+            .unwrap_or(line.trim_start());
+        for _ in 0..=blank_lines {
+            self.text.push('\n');
+            self.raw.push('\n');
+        }
+        self.text.push_str(content.trim_end());
+        self.raw.push_str(line);
+    }
+}
+
+/// A log section: the header line followed by timestamped entries
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// The heading line as it appears in the note
+    pub header: String,
+    pub category: Option<String>,
+    /// List type detected in the note, `None` if the section has no entries yet
+    pub list_type: Option<ListType>,
+    pub entries: Vec<LogEntry>,
+    configured_header: String,
+    /// The heading level of the header. A heading of the same or a higher level
+    /// ends the section.
+    level: usize,
+    bullet: char,
+    /// Labels of the table columns after the time and the entry, from the
+    /// table header in the note. `None` until a table header is seen.
+    table_columns: Option<Vec<String>>,
+    body: String,
+    /// The body split into entry places and the lines around them
+    parts: Vec<Part>,
+    modified: bool,
+    /// Whether the section was added to the note rather than read from it
+    inserted: bool,
+    /// Blank lines seen since the last entry line, while it may still be continued
+    continuation: Option<String>,
+}
+
+/// A piece of the body of a log section, in the order of the note
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    /// Lines the tool does not own, written back byte-for-byte
+    Text(String),
+    /// The place of an entry line, with its continuation lines
+    Entry,
+    /// The header and separator lines of the table of entries, as read
+    TableHeader(String),
+}
+
+impl Section {
+    fn new(line: &str, configured_header: &str, category: Option<&str>) -> Self {
+        Section {
+            header: line.to_string(),
+            category: category.map(|c| c.to_string()),
+            list_type: None,
+            entries: Vec::new(),
+            configured_header: configured_header.to_string(),
+            level: heading_level(line).unwrap_or(6),
+            bullet: DEFAULT_BULLET,
+            table_columns: None,
+            body: String::new(),
+            parts: Vec::new(),
+            modified: true,
+            inserted: true,
+            continuation: None,
+        }
+    }
+
+    /// Mark a section read from the note as complete
+    fn finish(&mut self) {
+        if let Some(blank_lines) = self.continuation.take() {
+            self.push_text(&blank_lines);
+        }
+        self.modified = false;
+        self.inserted = false;
+    }
+
+    /// Keep lines the tool does not own
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match self.parts.last_mut() {
+            Some(Part::Text(existing)) => existing.push_str(text),
+            _ => self.parts.push(Part::Text(text.to_string())),
+        }
+    }
+
+    fn push_line(&mut self, line: &str, format: Option<&EntryFormat>) {
+        self.body.push_str(line);
+        self.body.push('\n');
+
+        // Indented lines after a bullet entry continue that entry
+        if let Some(mut blank_lines) = self.continuation.take() {
+            if line.trim().is_empty() {
+                blank_lines.push_str(line);
+                blank_lines.push('\n');
+                self.continuation = Some(blank_lines);
+                return;
+            }
+            if line.starts_with([' ', '\t']) {
+                if let Some(entry) = self.entries.last_mut() {
+                    entry.push_continuation(line, blank_lines.lines().count());
+                }
+                self.continuation = Some(String::new());
+                return;
+            }
+            // Blank lines that are not followed by a continuation stay where they are
+            self.push_text(&blank_lines);
+        }
+
+        let category = self.category.clone();
+        if let Some(format) = format {
+            if let Some(entry) = LogEntry::parse_with_format(line, category.as_deref(), format) {
+                self.list_type = Some(ListType::Custom);
+                self.continuation = Some(String::new());
+                self.entries.push(entry);
+                self.parts.push(Part::Entry);
+                return;
+            }
+        }
+
+        let trimmed = line.trim();
+        if trimmed.starts_with('|') {
+            self.list_type = Some(ListType::Table);
+        } else if let Some(marker) = trimmed
+            .chars()
+            .next()
+            .filter(|c| ['*', '-', '+'].contains(c))
+        {
+            // Tasks are always written with `-`, so the bullet of the section is
+            // that of its first plain entry
+            let task = CHECKBOX_PATTERN.is_match(trimmed[1..].trim_start());
+            if !task && self.entries.iter().all(|e| e.task.is_some()) {
+                self.bullet = marker;
+            }
+            self.list_type = Some(ListType::Bullet);
+        }
+
+        if let Some(mut entry) = LogEntry::parse(line, category.as_deref()) {
+            if trimmed.starts_with('|') {
+                // Cells beyond the header get unlabeled columns, so they are kept
+                let cells = table_cells(trimmed);
+                let labels = self.table_columns.get_or_insert_with(Vec::new);
+                if labels.len() < cells.len() - 2 {
+                    labels.resize(cells.len() - 2, String::new());
+                }
+                entry.columns = labels
+                    .iter()
+                    .zip(&cells[2..])
+                    .map(|(label, cell)| (label.clone(), cell.clone()))
+                    .collect();
+            } else {
+                self.continuation = Some(String::new());
+            }
+            self.entries.push(entry);
+            self.parts.push(Part::Entry);
+            return;
+        }
+
+        if trimmed.starts_with('|') {
+            let cells = table_cells(trimmed);
+            let separator = cells.iter().all(|cell| TABLE_SEPARATOR_CELL.is_match(cell));
+            match self.parts.last_mut() {
+                // The separator below the header belongs to the table header
+                Some(Part::TableHeader(header)) if separator && header.lines().count() == 1 => {
+                    header.push_str(line);
+                    header.push('\n');
+                    return;
+                }
+                _ if !separator && self.table_columns.is_none() => {
+                    self.table_columns = Some(cells.iter().skip(2).cloned().collect());
+                    self.parts.push(Part::TableHeader(format!("{}\n", line)));
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.push_text(&format!("{}\n", line));
+    }
+
+    /// Add an entry, keeping the entries sorted by time.
+    /// If the timestamp is already taken, seconds are incremented until it is unique.
+    /// Returns the timestamp the entry was stored with.
+    pub fn insert(&mut self, time: NaiveTime, text: &str) -> NaiveTime {
+        self.insert_entry(None, time, text, None, Vec::new())
+    }
+
+    /// Add an open task, like [`Section::insert`]
+    pub fn insert_task(&mut self, time: NaiveTime, text: &str) -> NaiveTime {
+        self.insert_entry(None, time, text, Some(TaskStatus::Open), Vec::new())
+    }
+
+    /// Add an entry with a task status and cells for the extra table columns,
+    /// like [`Section::insert`]. Entries of weekly, monthly and yearly notes have
+    /// a `date`, and are sorted and kept unique by date and time.
+    pub fn insert_entry(
+        &mut self,
+        date: Option<NaiveDate>,
+        time: NaiveTime,
+        text: &str,
+        task: Option<TaskStatus>,
+        columns: Vec<(String, String)>,
+    ) -> NaiveTime {
+        let mut final_time = time;
+        while self
+            .entries
+            .iter()
+            .any(|e| e.date == date && e.time == final_time)
+        {
+            final_time += Duration::seconds(1);
+        }
+
+        self.entries.push(LogEntry {
+            date,
+            time: final_time,
+            text: text.to_string(),
+            task,
+            columns,
+            category: self.category.clone(),
+            raw: String::new(),
+        });
+        self.entries.sort_by_key(|e| (e.date, e.time));
+        self.modified = true;
+        final_time
+    }
+
+    /// Remove the entry at `index` and return it
+    pub fn remove(&mut self, index: usize) -> LogEntry {
+        self.modified = true;
+        self.entries.remove(index)
+    }
+
+    /// Replace the text and/or timestamp of the entry at `index`.
+    /// The entry is re-sorted, and a taken timestamp is made unique as in [`Section::insert`].
+    /// A `Duration` cell that was filled in from the `End` cell follows the new time.
+    /// Returns the timestamp the entry was stored with.
+    pub fn update(
+        &mut self,
+        index: usize,
+        text: Option<&str>,
+        time: Option<NaiveTime>,
+    ) -> NaiveTime {
+        let mut entry = self.entries.remove(index);
+        if let Some(time) = time {
+            update_duration(&mut entry.columns, entry.time, time);
+        }
+        self.insert_entry(
+            entry.date,
+            time.unwrap_or(entry.time),
+            text.unwrap_or(entry.text.as_str()),
+            entry.task,
+            entry.columns.clone(),
+        )
+    }
+
+    /// Tick off the task at `index`, appending `✅ <stamp>` to its text if a
+    /// completion stamp is given. A task that is already done is left as it is.
+    pub fn complete(&mut self, index: usize, stamp: Option<&str>) {
+        let entry = &mut self.entries[index];
+        if entry.task == Some(TaskStatus::Done) {
+            return;
+        }
+        entry.task = Some(TaskStatus::Done);
+        if let Some(stamp) = stamp {
+            entry.text = format!("{} ✅ {}", entry.text, stamp);
+        }
+        self.modified = true;
+    }
+
+    /// Render the entries as markdown lines in the given list type.
+    /// The table header and separator are only included if `include_header` is set.
+    /// Multi-line entries are rendered as one string: indented continuation lines
+    /// below the bullet, or joined with `<br>` in a table cell.
+    pub fn render_entries(
+        &self,
+        list_type: &ListType,
+        config: &Config,
+        include_header: bool,
+    ) -> Vec<String> {
+        self.render_lines(list_type, config, include_header, true)
+    }
+
+    /// Render the entries as markdown lines. Table cells are padded to the width
+    /// of their column if `padded` is set, as when listing; notes get compact rows.
+    fn render_lines(
+        &self,
+        list_type: &ListType,
+        config: &Config,
+        include_header: bool,
+        padded: bool,
+    ) -> Vec<String> {
+        let times = self.entries.iter().map(|e| {
+            let time = format_time(e.time, &config.time_format);
+            match e.date {
+                Some(date) => format!("{} {}", date.format("%Y-%m-%d"), time),
+                None => time,
+            }
+        });
+
+        match (list_type, &config.entry_format) {
+            (ListType::Custom, Some(format)) => times
+                .zip(&self.entries)
+                .enumerate()
+                .map(|(i, (time, e))| {
+                    format.render(i + 1, &time, &indent_continuation(&e.text), e.task)
+                })
+                .collect(),
+            // Without an entry_format, custom sections are written as bullets
+            (ListType::Bullet | ListType::Custom, _) => times
+                .zip(&self.entries)
+                .map(|(time, e)| {
+                    let text = indent_continuation(&e.text);
+                    match e.task {
+                        Some(task) => {
+                            format!("{} {} {} {}", TASK_BULLET, task.checkbox(), time, text)
+                        }
+                        None => format!("{} {} {}", self.bullet, time, text),
+                    }
+                })
+                .collect(),
+            (ListType::Table, _) => {
+                let labels = self.column_labels(config);
+                let rows: Vec<Vec<String>> = times
+                    .zip(&self.entries)
+                    .map(|(time, e)| {
+                        let text = e.text.replace('\n', "<br>");
+                        let text = match e.task {
+                            Some(task) => format!("{} {}", task.checkbox(), text),
+                            None => text,
+                        };
+                        let mut row = vec![time, escape_cell(&text)];
+                        row.extend(
+                            labels
+                                .iter()
+                                .map(|label| escape_cell(e.column(label).unwrap_or_default())),
+                        );
+                        row
+                    })
+                    .collect();
+                let (time_label, event_label) = self.time_and_event_labels(config);
+                let header: Vec<String> = [time_label, event_label]
+                    .into_iter()
+                    .chain(labels)
+                    .map(|label| escape_cell(&label))
+                    .collect();
+                let mut widths: Vec<usize> = header.iter().map(|cell| cell.width()).collect();
+                for row in &rows {
+                    for (width, cell) in widths.iter_mut().zip(row) {
+                        *width = (*width).max(cell.width());
+                    }
+                }
+
+                let mut lines = Vec::new();
+                let cell_widths = if padded { widths.clone() } else { Vec::new() };
+                if include_header {
+                    lines.push(format_table_row(&header, &cell_widths));
+                    lines.push(format_table_separator(&widths, padded));
+                }
+                lines.extend(rows.iter().map(|row| format_table_row(row, &cell_widths)));
+                lines
+            }
+        }
+    }
+
+    /// Labels of the time and entry columns: those of the table in the note,
+    /// unless `time_label` and `event_label` are configured
+    fn time_and_event_labels(&self, config: &Config) -> (String, String) {
+        let from_note = self.parts.iter().find_map(|part| match part {
+            Part::TableHeader(header) => match table_cells(header.lines().next()?).as_slice() {
+                [time, event, ..] => Some((time.clone(), event.clone())),
+                _ => None,
+            },
+            _ => None,
+        });
+        match from_note {
+            Some(labels) if !config.labels_configured() => labels,
+            _ => (config.time_label.clone(), config.event_label.clone()),
+        }
+    }
+
+    /// Labels of the table columns after the time and the entry: those of the
+    /// table in the note, followed by configured `table_columns` it does not have
+    pub fn column_labels(&self, config: &Config) -> Vec<String> {
+        let mut labels = self.table_columns.clone().unwrap_or_default();
+        for label in &config.table_columns {
+            if !labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+                labels.push(label.clone());
+            }
+        }
+        labels
+    }
+
+    /// Write the section back to the note after it was modified. The entries are
+    /// written to the places of the entries that were read, in order, with
+    /// entries beyond those after the last one. Everything else in the section
+    /// is written as it was read. A section without entries gets its entries
+    /// after its text.
+    fn render_into(&self, out: &mut String, config: &Config, last: bool) {
+        if self.inserted && !out.is_empty() {
+            *out = out.trim_end().to_string() + "\n\n";
+        }
+        out.push_str(&self.header);
+        out.push('\n');
+
+        let list_type = self.effective_list_type(config);
+        let table = list_type == ListType::Table;
+        let mut lines = self.render_lines(&list_type, config, true, false);
+        let table_header: Vec<String> = if table {
+            lines.drain(..2).collect()
+        } else {
+            Vec::new()
+        };
+        let has_table_header = self
+            .parts
+            .iter()
+            .any(|part| matches!(part, Part::TableHeader(_)));
+        let slots = self
+            .parts
+            .iter()
+            .filter(|part| **part == Part::Entry)
+            .count();
+
+        if slots == 0 {
+            // A table header without rows, as in a template, gets the rows below it
+            if table && has_table_header && !self.entries.is_empty() {
+                for part in &self.parts {
+                    match part {
+                        Part::Text(text) => out.push_str(text),
+                        Part::TableHeader(_) => {
+                            for line in table_header.iter().chain(&lines) {
+                                out.push_str(line);
+                                out.push('\n');
+                            }
+                        }
+                        Part::Entry => {}
+                    }
+                }
+                return;
+            }
+            let text: String = self
+                .parts
+                .iter()
+                .map(|part| match part {
+                    Part::Text(text) | Part::TableHeader(text) => text.as_str(),
+                    Part::Entry => "",
+                })
+                .collect();
+            if self.entries.is_empty() {
+                out.push_str(&text);
+                return;
+            }
+            // The entries follow the text, without its trailing blank lines
+            let end = text
+                .split_inclusive('\n')
+                .scan(0, |offset, line| {
+                    *offset += line.len();
+                    Some((*offset, line))
+                })
+                .filter(|(_, line)| !line.trim().is_empty())
+                .last()
+                .map_or(0, |(offset, _)| offset);
+            out.push_str(&text[..end]);
+            out.push('\n');
+            for line in table_header.iter().chain(&lines) {
+                out.push_str(line);
+                out.push('\n');
+            }
+            if !last {
+                out.push('\n');
+            }
+            return;
+        }
+
+        let mut entries = lines.into_iter();
+        let mut slot = 0;
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::TableHeader(text) if !table => out.push_str(text),
+                Part::TableHeader(_) => {
+                    for line in &table_header {
+                        out.push_str(line);
+                        out.push('\n');
+                    }
+                }
+                Part::Entry => {
+                    slot += 1;
+                    if slot == 1 && table && !has_table_header {
+                        for line in &table_header {
+                            out.push_str(line);
+                            out.push('\n');
+                        }
+                    }
+                    let take = if slot == slots { usize::MAX } else { 1 };
+                    for line in entries.by_ref().take(take) {
+                        out.push_str(&line);
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+    }
+
+    /// The list type used when writing this section back to the note
+    fn effective_list_type(&self, config: &Config) -> ListType {
+        match &self.list_type {
+            Some(list_type) if !self.entries.is_empty() => list_type.clone(),
+            _ => config.list_type.clone(),
+        }
+    }
+}
+
+enum Block {
+    Text(String),
+    Section(Section),
+}
+
+/// A daily note parsed into front matter, free text and log sections.
+///
+/// Only the log sections configured through `section_header` and
+/// `section_header_<category>` are parsed into entries. Everything else is kept
+/// as text, and sections that are not modified are written back unchanged. In
+/// modified sections only the entry lines are rewritten: other lists, comments,
+/// images and paragraphs in the section stay where they are.
+pub struct DailyLog {
+    /// The properties between the leading `---` lines, if the note has front matter
+    pub front_matter: Option<FrontMatter>,
+    blocks: Vec<Block>,
+}
+
+impl DailyLog {
+    /// Parse a note, recognising the log sections defined in the configuration
+    pub fn parse(content: &str, config: &Config) -> Self {
+        let mut headers: Vec<(&str, Option<&str>)> = vec![(config.section_header.as_str(), None)];
+        for (key, header) in &config.category_headers {
+            if let Some(category) = key.strip_prefix("section_header_") {
+                headers.push((header.as_str(), Some(category)));
+            }
+        }
+        Self::parse_with_headers(content, &headers, config)
+    }
+
+    pub(crate) fn parse_with_headers(
+        content: &str,
+        headers: &[(&str, Option<&str>)],
+        config: &Config,
+    ) -> Self {
+        let format = config.entry_format.as_ref();
+        let patterns: Vec<Option<Regex>> = headers
+            .iter()
+            .map(|(_, category)| {
+                config
+                    .section_patterns
+                    .get(category.unwrap_or("default"))
+                    .and_then(|pattern| Regex::new(pattern).ok())
+            })
+            .collect();
+        let end_marker = config
+            .section_end
+            .as_deref()
+            .map(str::trim)
+            .filter(|marker| !marker.is_empty());
+        let (front_matter, body) = split_front_matter(content);
+
+        let mut blocks = Vec::new();
+        let mut text = String::new();
+        let mut current: Option<Section> = None;
+
+        for line in body.lines() {
+            let level = heading_level(line);
+            // The configured header itself takes precedence over patterns
+            let heading = line.trim_end();
+            let matched = headers
+                .iter()
+                .find(|(header, _)| !header.is_empty() && heading == header.trim_end())
+                .or_else(|| {
+                    level?;
+                    headers
+                        .iter()
+                        .zip(&patterns)
+                        .find(|(_, pattern)| pattern.as_ref().is_some_and(|p| p.is_match(heading)))
+                        .map(|(header, _)| header)
+                });
+
+            if let Some((header, category)) = matched {
+                if let Some(mut section) = current.take() {
+                    section.finish();
+                    blocks.push(Block::Section(section));
+                } else if !text.is_empty() {
+                    blocks.push(Block::Text(std::mem::take(&mut text)));
+                }
+                current = Some(Section::new(line, header, *category));
+                continue;
+            }
+
+            if let Some(section) = current.as_mut() {
+                // Subheadings belong to the section, the end marker belongs to the text after it
+                let ends = match level {
+                    Some(level) => level <= section.level,
+                    None => end_marker.is_some_and(|marker| line.trim() == marker),
+                };
+                if !ends {
+                    section.push_line(line, format);
+                    continue;
+                }
+                let mut section = current.take().unwrap();
+                section.finish();
+                blocks.push(Block::Section(section));
+            }
+
+            text.push_str(line);
+            text.push('\n');
+        }
+
+        if let Some(mut section) = current.take() {
+            section.finish();
+            blocks.push(Block::Section(section));
+        }
+        if !text.is_empty() {
+            blocks.push(Block::Text(text));
+        }
+
+        DailyLog {
+            front_matter: front_matter.map(|raw| FrontMatter::parse(&raw)),
+            blocks,
+        }
+    }
+
+    /// Read and parse the note at `path`. Returns `None` if the note does not exist.
+    pub fn load(path: &Path, config: &Config) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = read_to_string(path).map_err(|e| Error::io(path, e))?;
+        Ok(Some(Self::parse(&content, config)))
+    }
+
+    /// Create a new note for `date` from the configured template, with the
+    /// warnings of the template
+    pub fn from_template(config: &Config, date: NaiveDate) -> Result<(Self, Vec<Warning>)> {
+        let (content, warnings) = get_template_content_for_date(config, date)?;
+        Ok((Self::parse(&content, config), warnings))
+    }
+
+    /// Create the note that `category` is logged to for `date`, which is a
+    /// weekly, monthly or yearly note if one is configured for the category
+    pub fn from_template_for_category(
+        config: &Config,
+        category: Option<&str>,
+        date: NaiveDate,
+    ) -> Result<(Self, Vec<Warning>)> {
+        let (content, warnings) = get_template_content_for_category(config, category, date)?;
+        Ok((Self::parse(&content, config), warnings))
+    }
+
+    /// All log sections in the order they appear in the note
+    pub fn sections(&self) -> impl Iterator<Item = &Section> {
+        self.blocks.iter().filter_map(|block| match block {
+            Block::Section(section) => Some(section),
+            Block::Text(_) => None,
+        })
+    }
+
+    /// All entries in all log sections
+    pub fn entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.sections().flat_map(|section| section.entries.iter())
+    }
+
+    /// The log section with the given header, if present
+    pub fn section(&self, header: &str) -> Option<&Section> {
+        self.sections().find(|s| s.configured_header == header)
+    }
+
+    /// Mutable access to a log section. The section is re-rendered when the note is written.
+    pub fn section_mut(&mut self, header: &str) -> Option<&mut Section> {
+        self.blocks.iter_mut().find_map(|block| match block {
+            Block::Section(section) if section.configured_header == header => {
+                section.modified = true;
+                Some(section)
+            }
+            _ => None,
+        })
+    }
+
+    /// Mutable access to a log section, appending it to the note if it is missing
+    pub fn section_or_insert(&mut self, header: &str, category: Option<&str>) -> &mut Section {
+        if self.section(header).is_none() {
+            self.blocks
+                .push(Block::Section(Section::new(header, header, category)));
+        }
+        self.section_mut(header).unwrap()
+    }
+
+    /// Set a front matter property, adding front matter to the note if it has none.
+    /// Fails if the existing front matter is not valid YAML.
+    pub fn set_property(
+        &mut self,
+        key: &str,
+        value: serde_yaml::Value,
+    ) -> std::result::Result<(), String> {
+        self.front_matter
+            .get_or_insert_with(|| FrontMatter::parse(""))
+            .set(key, value)
+    }
+
+    /// Update the properties configured under `front_matter`: the time of the
+    /// change, the number of entries in each log section and the tags used in entries.
+    /// Fails if the existing front matter is not valid YAML.
+    pub fn update_front_matter(
+        &mut self,
+        config: &Config,
+        now: NaiveDateTime,
+    ) -> std::result::Result<(), String> {
+        let settings = &config.front_matter;
+        if let Some(key) = &settings.entries {
+            let counts: Vec<(String, usize)> = self
+                .sections()
+                .map(|section| {
+                    let key = match &section.category {
+                        Some(category) => format!("{}_{}", key, category),
+                        None => key.clone(),
+                    };
+                    (key, section.entries.len())
+                })
+                .collect();
+            for (key, count) in counts {
+                self.set_property(&key, count.into())?;
+            }
+        }
+        if let Some(key) = &settings.tags {
+            let tags: Vec<String> = self
+                .entries()
+                .flat_map(|entry| collect_tags(&entry.text))
+                .collect();
+            if !tags.is_empty() {
+                self.front_matter
+                    .get_or_insert_with(|| FrontMatter::parse(""))
+                    .add_tags(key, &tags)?;
+            }
+        }
+        if let Some(key) = &settings.updated {
+            let updated = now.format("%Y-%m-%dT%H:%M:%S").to_string();
+            self.set_property(key, updated.into())?;
+        }
+        Ok(())
+    }
+
+    /// Split the note as read into the text before the given section, the section
+    /// itself and the text after it. Without the section, all text is "before".
+    pub(crate) fn split_at_section(&self, header: &str) -> (String, Option<&Section>, String) {
+        let mut before = String::new();
+        if let Some(front_matter) = &self.front_matter {
+            before = format!("---\n{}---\n", front_matter.raw());
+        }
+        let mut after = String::new();
+        let mut found = None;
+
+        for block in &self.blocks {
+            let target = if found.is_some() {
+                &mut after
+            } else {
+                &mut before
+            };
+            match block {
+                Block::Text(text) => target.push_str(text),
+                Block::Section(section)
+                    if found.is_none() && section.configured_header == header =>
+                {
+                    found = Some(section)
+                }
+                Block::Section(section) => {
+                    target.push_str(&section.header);
+                    target.push('\n');
+                    target.push_str(&section.body);
+                }
+            }
+        }
+
+        (before, found, after)
+    }
+
+    /// Render the note back to markdown. Unmodified content is written as it was read.
+    pub fn render(&self, config: &Config) -> String {
+        let mut out = String::new();
+        if let Some(front_matter) = &self.front_matter {
+            out.push_str("---\n");
+            out.push_str(&front_matter.render());
+            out.push_str("---\n");
+        }
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            match block {
+                Block::Text(text) => out.push_str(text),
+                Block::Section(section) if !section.modified => {
+                    out.push_str(&section.header);
+                    out.push('\n');
+                    out.push_str(&section.body);
+                }
+                Block::Section(section) => {
+                    section.render_into(&mut out, config, i + 1 == self.blocks.len())
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// The level of a markdown heading, 1 for `# Title` up to 6
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.bytes().take_while(|&b| b == b'#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with([' ', '\t'])))
+        .then_some(level)
+}
+
+/// Parse the timestamp of an entry: its time, preceded by the date in weekly,
+/// monthly and yearly notes (`2026-10-15 21:05:00`)
+fn parse_stamp(stamp: &str) -> Option<(Option<NaiveDate>, NaiveTime)> {
+    let stamp = stamp.trim();
+    if let Some((date, time)) = stamp.split_once(char::is_whitespace) {
+        if let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            return Some((Some(date), parse_time(time.trim())?));
+        }
+    }
+    Some((None, parse_time(stamp)?))
+}
+
+/// Recompute the `Duration` cell of an entry moved from `old` to `new`, if it
+/// holds the time until the `End` cell as filled in when the entry was added.
+/// A duration that was given by hand is kept.
+fn update_duration(columns: &mut [(String, String)], old: NaiveTime, new: NaiveTime) {
+    let Some(end) = columns
+        .iter()
+        .find(|(label, _)| label.eq_ignore_ascii_case("end"))
+        .and_then(|(_, value)| parse_time(value))
+    else {
+        return;
+    };
+    if let Some((_, duration)) = columns
+        .iter_mut()
+        .find(|(label, _)| label.eq_ignore_ascii_case("duration"))
+    {
+        if *duration == format_duration(end - old) {
+            *duration = format_duration(end - new);
+        }
+    }
+}
+
+/// Indent the lines after the first, so they continue a list item
+fn indent_continuation(text: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => line.to_string(),
+            _ if line.is_empty() => String::new(),
+            _ => format!("{}{}", CONTINUATION_INDENT, line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn split_front_matter(content: &str) -> (Option<String>, &str) {
+    if let Some(rest) = content.strip_prefix("---\n") {
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            if line.trim_end() == "---" {
+                let front_matter = rest[..offset].to_string();
+                return (Some(front_matter), &rest[offset + line.len()..]);
+            }
+            offset += line.len();
+        }
+    }
+    (None, content)
+}
+
+/// Format a table row, padding each cell to the width of its column. Widths are
+/// display widths, so wide characters such as emoji and CJK count as two columns.
+/// Cells without a width are not padded.
+pub(crate) fn format_table_row(cells: &[String], widths: &[usize]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let width = widths.get(i).copied().unwrap_or(0);
+            let padding = width.saturating_sub(cell.width());
+            format!(" {}{} ", cell, " ".repeat(padding))
+        })
+        .collect();
+    format!("|{}|", cells.join("|"))
+}
+
+/// Format a table separator line with given column widths. A padded separator
+/// spans the whole column; otherwise it is set off by spaces like compact rows.
+pub(crate) fn format_table_separator(widths: &[usize], padded: bool) -> String {
+    let cells: Vec<String> = widths
+        .iter()
+        .map(|width| {
+            if padded {
+                "-".repeat(width + 2)
+            } else {
+                format!(" {} ", "-".repeat(*width))
+            }
+        })
+        .collect();
+    format!("|{}|", cells.join("|"))
+}
+
+/// Escape the pipes in the text of a table cell, including those of wikilinks
+/// such as `[[note|alias]]`, which Obsidian reads as `[[note\|alias]]` in tables
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Whether the wikilink opened just before `rest` is closed in the same cell.
+/// Only the pipe of an alias (`[[note|alias]]`) may come before its `]]`.
+fn closes_link(rest: &str) -> bool {
+    let mut pipes = 0;
+    let mut chars = rest.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if rest[i..].starts_with("\\|") {
+            chars.next();
+        } else if rest[i..].starts_with("]]") {
+            return true;
+        } else if c == '|' {
+            pipes += 1;
+            if pipes > 1 {
+                return false;
+            }
+        }
+    }
+    false
+}
+
+/// The trimmed cells of a table row, without the outer pipes. Escaped pipes
+/// (`\|`) and pipes inside wikilinks do not separate cells, and `\|` is read as `|`.
+fn table_cells(row: &str) -> Vec<String> {
+    let row = row.trim();
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_link = false;
+    let mut after_separator = false;
+    let mut rest = row;
+    while let Some(c) = rest.chars().next() {
+        let mut length = c.len_utf8();
+        after_separator = false;
+        if rest.starts_with("\\|") {
+            cell.push('|');
+            length = 2;
+        } else if !in_link && rest.starts_with("[[") && closes_link(&rest[2..]) {
+            in_link = true;
+            cell.push_str("[[");
+            length = 2;
+        } else if in_link && rest.starts_with("]]") {
+            in_link = false;
+            cell.push_str("]]");
+            length = 2;
+        } else if c == '|' && !in_link {
+            cells.push(std::mem::take(&mut cell).trim().to_string());
+            after_separator = true;
+        } else {
+            cell.push(c);
+        }
+        rest = &rest[length..];
+    }
+    // The closing pipe of the row does not start another cell
+    if !after_separator {
+        cells.push(cell.trim().to_string());
+    }
+    cells
+}
@@ -0,0 +1,176 @@
+use crate::document::TaskStatus;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    static ref PLACEHOLDER: Regex = Regex::new(r"\{([a-z]*)\}").unwrap();
+}
+
+/// The pattern matching a timestamp, as accepted by `parse_time`, and the date
+/// before it in weekly, monthly and yearly notes
+const TIME_PATTERN: &str = r"(?:\d{4}-\d{2}-\d{2}\s+)?\d{1,2}:\d{2}(?::\d{2})?(?:\s*[AaPp][Mm])?";
+
+/// A user-defined entry line, such as `- **{time}** — {text}` or `{n}. [{time}] {text}`.
+///
+/// `{time}` and `{text}` are required. `{n}` is the position of the entry in the
+/// section, for numbered lists, and `{task}` is where the checkbox of a task
+/// goes (`[ ] ` or `[x] `). Without `{task}`, the checkbox is written at the
+/// start of the text. Lines are parsed with a pattern derived from the same
+/// format, where spaces match any amount of whitespace.
+#[derive(Debug, Clone)]
+pub struct EntryFormat {
+    format: String,
+    parser: Regex,
+}
+
+impl PartialEq for EntryFormat {
+    fn eq(&self, other: &Self) -> bool {
+        self.format == other.format
+    }
+}
+
+impl EntryFormat {
+    /// Check a format and derive its parser. Fails with a description of the
+    /// problem if `{time}` or `{text}` is missing or repeated, or a placeholder
+    /// is not known.
+    pub fn new(format: &str) -> Result<Self, String> {
+        let mut pattern = String::from(r"^\s*");
+        let mut counts = [0; 4];
+        let mut last = 0;
+        // The checkbox includes its space, so a space after `{task}` is not needed
+        let normalized = format.trim().replace("{task} ", "{task}");
+        for captures in PLACEHOLDER.captures_iter(&normalized) {
+            let placeholder = captures.get(0).unwrap();
+            pattern.push_str(&literal_pattern(&normalized[last..placeholder.start()]));
+            last = placeholder.end();
+            let (index, group) = match &captures[1] {
+                "time" => (0, format!("(?P<time>{})", TIME_PATTERN)),
+                "text" => (1, r"(?P<text>.*?\S.*?)".to_string()),
+                "n" => (2, r"\d+".to_string()),
+                "task" => (3, r"(?:\[(?P<task>[ xX])\]\s+)?".to_string()),
+                other => {
+                    return Err(format!(
+                        "unknown placeholder '{{{}}}' in line format '{}', use {{time}}, {{text}}, {{n}} or {{task}}",
+                        other, format
+                    ))
+                }
+            };
+            counts[index] += 1;
+            pattern.push_str(&group);
+        }
+        pattern.push_str(&literal_pattern(&normalized[last..]));
+        pattern.push_str(r"\s*$");
+
+        for (index, name) in ["time", "text", "n", "task"].iter().enumerate() {
+            let required = index < 2;
+            if (required && counts[index] != 1) || counts[index] > 1 {
+                return Err(format!(
+                    "line format '{}' must contain {{{}}} {}",
+                    format,
+                    name,
+                    if required {
+                        "exactly once"
+                    } else {
+                        "at most once"
+                    }
+                ));
+            }
+        }
+
+        let parser = Regex::new(&pattern).map_err(|e| e.to_string())?;
+        Ok(EntryFormat {
+            format: format.to_string(),
+            parser,
+        })
+    }
+
+    /// The format as configured
+    pub fn as_str(&self) -> &str {
+        &self.format
+    }
+
+    /// Whether the format places the checkbox of tasks itself
+    pub fn has_task(&self) -> bool {
+        self.format.contains("{task}")
+    }
+
+    /// Render an entry. `number` is the 1-based position used for `{n}`.
+    pub fn render(
+        &self,
+        number: usize,
+        time: &str,
+        text: &str,
+        task: Option<TaskStatus>,
+    ) -> String {
+        let checkbox = task
+            .map(|task| format!("{} ", task.checkbox()))
+            .unwrap_or_default();
+        let text = if self.has_task() {
+            text.to_string()
+        } else {
+            format!("{}{}", checkbox, text)
+        };
+        let format = self.format.trim().replace("{task} ", "{task}");
+        PLACEHOLDER
+            .replace_all(&format, |captures: &regex::Captures| match &captures[1] {
+                "time" => time.to_string(),
+                "text" => text.clone(),
+                "n" => number.to_string(),
+                "task" => checkbox.clone(),
+                _ => captures[0].to_string(),
+            })
+            .into_owned()
+    }
+
+    /// Split a line into its timestamp, text and the status of a `{task}`
+    /// checkbox. Returns `None` if the line does not have this format.
+    pub fn parse<'a>(&self, line: &'a str) -> Option<(&'a str, &'a str, Option<TaskStatus>)> {
+        let captures = self.parser.captures(line)?;
+        let task = captures
+            .name("task")
+            .map(|mark| TaskStatus::from_checkbox(mark.as_str()));
+        Some((
+            captures.name("time")?.as_str(),
+            captures.name("text")?.as_str().trim(),
+            task,
+        ))
+    }
+}
+
+impl Serialize for EntryFormat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.format)
+    }
+}
+
+impl<'de> Deserialize<'de> for EntryFormat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let format = String::deserialize(deserializer)?;
+        EntryFormat::new(&format).map_err(serde::de::Error::custom)
+    }
+}
+
+impl std::fmt::Display for EntryFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format)
+    }
+}
+
+/// The pattern matching literal text of a format, where spaces match any whitespace
+fn literal_pattern(literal: &str) -> String {
+    let mut pattern = String::new();
+    for (i, part) in literal.split(' ').enumerate() {
+        if i > 0 && !pattern.ends_with(r"\s+") {
+            pattern.push_str(r"\s+");
+        }
+        pattern.push_str(&regex::escape(part));
+    }
+    pattern
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors returned by the library API
+#[derive(Debug)]
+pub enum Error {
+    /// Reading, writing or creating a file or directory failed
+    Io { path: PathBuf, source: io::Error },
+    /// The configuration file could not be parsed
+    ConfigParse {
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    /// The configuration parsed, but is not usable
+    ConfigInvalid {
+        path: Option<PathBuf>,
+        problems: Vec<String>,
+    },
+    /// An entry or its arguments could not be understood
+    InvalidEntry(String),
+    /// The editor could not be started
+    EditorLaunch { editor: String, source: io::Error },
+    /// The editor ran but exited with a non-zero exit code
+    EditorFailed { editor: String, code: Option<i32> },
+    /// No entry matches the time, index or `last` given to amend or delete
+    EntryNotFound(String),
+    /// The requested phrase is not defined in the configuration
+    PhraseNotFound(String),
+    /// A search pattern is not a valid regular expression
+    InvalidPattern { pattern: String, message: String },
+    /// The template file exists but could not be read
+    Template { path: PathBuf, source: io::Error },
+    /// The front matter of a note is not valid YAML, or a property is invalid
+    FrontMatter { path: PathBuf, message: String },
+}
+
+/// Result type used throughout the library
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A problem that did not stop a command, returned for the caller to report
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    /// The file or folder the warning is about
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Warning {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
+impl Error {
+    /// Build an I/O error that remembers which path was involved
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::ConfigParse {
+                path,
+                line,
+                column,
+                message,
+            } => {
+                write!(f, "Invalid configuration in {}", path.display())?;
+                if let Some(line) = line {
+                    write!(f, ":{}", line)?;
+                    if let Some(column) = column {
+                        write!(f, ":{}", column)?;
+                    }
+                }
+                write!(f, ": {}", message)
+            }
+            Error::ConfigInvalid { path, problems } => {
+                match path {
+                    Some(path) => write!(f, "Invalid configuration in {}:", path.display())?,
+                    None => write!(f, "Invalid configuration:")?,
+                }
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+            Error::InvalidEntry(message) => write!(f, "Invalid entry: {}", message),
+            Error::EditorLaunch { editor, source } => {
+                write!(f, "Failed to start editor '{}': {}", editor, source)
+            }
+            Error::EditorFailed { editor, code } => match code {
+                Some(code) => write!(f, "Editor '{}' exited with code {}", editor, code),
+                None => write!(f, "Editor '{}' was terminated by a signal", editor),
+            },
+            Error::EntryNotFound(message) => write!(f, "Entry not found: {}", message),
+            Error::PhraseNotFound(key) => {
+                write!(f, "Phrase '{}' not found in configuration", key)
+            }
+            Error::InvalidPattern { pattern, message } => {
+                write!(f, "Invalid search pattern '{}': {}", pattern, message)
+            }
+            Error::Template { path, source } => {
+                write!(f, "Could not read template {}: {}", path.display(), source)
+            }
+            Error::FrontMatter { path, message } => {
+                write!(f, "Invalid front matter in {}: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. }
+            | Error::EditorLaunch { source, .. }
+            | Error::Template { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_yaml::{Mapping, Value};
+
+lazy_static! {
+    /// A top-level `key:` line of the YAML front matter
+    static ref TOP_LEVEL_KEY: Regex =
+        Regex::new(r#"^(?:"([^"]*)"|'([^']*)'|([^\s#'"\-][^:]*?))\s*:(?:\s|$)"#).unwrap();
+    /// An Obsidian `#tag`, which may contain letters, digits, `_`, `-` and `/`
+    static ref TAG: Regex = Regex::new(r"(?:^|\s)#([\p{L}\p{N}_/-]+)").unwrap();
+}
+
+/// The YAML front matter of a note, holding its Obsidian properties.
+///
+/// The front matter is written back exactly as it was read. When properties are
+/// changed, only the lines of those properties are replaced, so comments and the
+/// formatting of other properties are kept. New properties are appended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontMatter {
+    raw: String,
+    /// The parsed properties, or why the front matter is not a YAML mapping
+    properties: std::result::Result<Mapping, String>,
+    /// Properties changed since the front matter was read, in the order they were set
+    changed: Vec<String>,
+}
+
+impl FrontMatter {
+    /// Parse the text between the `---` lines
+    pub fn parse(raw: &str) -> Self {
+        let properties = match serde_yaml::from_str::<Value>(raw) {
+            Ok(Value::Mapping(mapping)) => Ok(mapping),
+            Ok(Value::Null) => Ok(Mapping::new()),
+            Ok(_) => Err("front matter is not a list of properties".to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        FrontMatter {
+            raw: raw.to_string(),
+            properties,
+            changed: Vec::new(),
+        }
+    }
+
+    /// The front matter as it was read
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// The properties, or `None` if the front matter is not valid YAML
+    pub fn properties(&self) -> Option<&Mapping> {
+        self.properties.as_ref().ok()
+    }
+
+    /// The value of a property
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.properties()?.get(key)
+    }
+
+    /// Set a property. Fails with the YAML error if the front matter could not be
+    /// parsed, since changing it could lose content.
+    pub fn set(&mut self, key: &str, value: Value) -> std::result::Result<(), String> {
+        let properties = self.properties.as_mut().map_err(|e| e.clone())?;
+        if properties.get(key) == Some(&value) {
+            return Ok(());
+        }
+        properties.insert(Value::String(key.to_string()), value);
+        if !self.changed.iter().any(|k| k == key) {
+            self.changed.push(key.to_string());
+        }
+        Ok(())
+    }
+
+    /// Add tags to the `key` property, keeping the tags that are already there.
+    /// A comma or space separated string is turned into a list when tags are added.
+    pub fn add_tags(&mut self, key: &str, tags: &[String]) -> std::result::Result<(), String> {
+        let mut existing: Vec<Value> = match self.get(key) {
+            Some(Value::Sequence(values)) => values.clone(),
+            Some(Value::String(text)) => text
+                .split([',', ' '])
+                .filter(|t| !t.is_empty())
+                .map(|t| Value::String(t.to_string()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let original = existing.len();
+        for tag in tags {
+            let tag = Value::String(tag.clone());
+            if !existing.contains(&tag) {
+                existing.push(tag);
+            }
+        }
+        if existing.len() == original {
+            return Ok(());
+        }
+        self.set(key, Value::Sequence(existing))
+    }
+
+    /// The YAML text to write between the `---` lines
+    pub fn render(&self) -> String {
+        let Ok(properties) = &self.properties else {
+            return self.raw.clone();
+        };
+        if self.changed.is_empty() {
+            return self.raw.clone();
+        }
+
+        let lines: Vec<&str> = self.raw.split_inclusive('\n').collect();
+        let mut out = String::new();
+        let mut written = Vec::new();
+        let mut i = 0;
+        while i < lines.len() {
+            let key = top_level_key(lines[i]);
+            let end = block_end(&lines, i);
+            match key.filter(|k| self.changed.contains(k)) {
+                Some(key) => {
+                    if let Some(value) = properties.get(key.as_str()) {
+                        out.push_str(&render_property(&key, value));
+                    }
+                    written.push(key);
+                }
+                None => lines[i..end].iter().for_each(|line| out.push_str(line)),
+            }
+            i = end;
+        }
+
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        for key in self.changed.iter().filter(|k| !written.contains(k)) {
+            if let Some(value) = properties.get(key.as_str()) {
+                out.push_str(&render_property(key, value));
+            }
+        }
+        out
+    }
+}
+
+/// Tags (`#tag`) in a text, without the `#`, in order of appearance.
+/// Like Obsidian, tags made of digits only are ignored.
+pub fn collect_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for captures in TAG.captures_iter(text) {
+        let tag = captures[1].trim_end_matches(['/', '-']).to_string();
+        if !tag.chars().all(|c| c.is_ascii_digit()) && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+fn top_level_key(line: &str) -> Option<String> {
+    let captures = TOP_LEVEL_KEY.captures(line)?;
+    let key = captures
+        .get(1)
+        .or(captures.get(2))
+        .or(captures.get(3))?
+        .as_str();
+    Some(key.to_string())
+}
+
+/// The line after the property starting at line `start`, including its indented
+/// or listed values but not the blank lines after them
+fn block_end(lines: &[&str], start: usize) -> usize {
+    if top_level_key(lines[start]).is_none() {
+        return start + 1;
+    }
+    let mut end = start + 1;
+    let mut i = start + 1;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim().is_empty() {
+            i += 1;
+            continue;
+        }
+        if !(line.starts_with([' ', '\t']) || line.starts_with("- ") || line.trim_end() == "-") {
+            break;
+        }
+        i += 1;
+        end = i;
+    }
+    end
+}
+
+fn render_property(key: &str, value: &Value) -> String {
+    let mut property = Mapping::new();
+    property.insert(Value::String(key.to_string()), value.clone());
+    serde_yaml::to_string(&property).unwrap_or_default()
+}
@@ -0,0 +1,683 @@
+use crate::error::{Error, Result};
+use crate::locale::language;
+use chrono::Locale;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Built-in translations, one bundle per language. Keys missing from a bundle
+/// fall back to English.
+///
+/// `{name}` in a message is replaced by [`Messages::format`].
+const ENGLISH: &[(&str, &str)] = &[
+    ("time_label", "Time"),
+    ("event_label", "Event"),
+    ("conjunction", "and"),
+    ("logged", "Logged."),
+    ("removed", "Removed: {entry}"),
+    ("changed", "Changed: {old} -> {new}"),
+    ("done", "Done: {entry}"),
+    ("properties_set", "Set {properties}."),
+    ("no_log_found", "No log found for {date}"),
+    ("no_entries_for_day", "No entries found for {date}"),
+    (
+        "no_entries_for_categories",
+        "No entries found for the specified categories.",
+    ),
+    (
+        "no_entries_in_range",
+        "No entries found from {from} to {to}",
+    ),
+    ("no_search_matches", "No entries found matching '{pattern}'"),
+    ("entries_for_day", "Log entries for {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Log entries for {date} (all categories):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Log entries for {date} (categories: {categories}):",
+    ),
+    ("weekly_note", "{header} (weekly note of {date})"),
+    ("monthly_note", "{header} (monthly note of {date})"),
+    ("yearly_note", "{header} (yearly note of {date})"),
+    ("using_profile", "Using profile '{profile}'"),
+];
+
+const NORWEGIAN_BOKMAL: &[(&str, &str)] = &[
+    ("time_label", "Tidspunkt"),
+    ("event_label", "Hendelse"),
+    ("conjunction", "og"),
+    ("logged", "Logget."),
+    ("removed", "Fjernet: {entry}"),
+    ("changed", "Endret: {old} -> {new}"),
+    ("done", "Fullført: {entry}"),
+    ("properties_set", "Satte {properties}."),
+    ("no_log_found", "Fant ingen logg for {date}"),
+    ("no_entries_for_day", "Ingen oppføringer for {date}"),
+    (
+        "no_entries_for_categories",
+        "Ingen oppføringer for de valgte kategoriene.",
+    ),
+    (
+        "no_entries_in_range",
+        "Ingen oppføringer fra {from} til {to}",
+    ),
+    (
+        "no_search_matches",
+        "Ingen oppføringer samsvarer med '{pattern}'",
+    ),
+    ("entries_for_day", "Loggoppføringer for {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Loggoppføringer for {date} (alle kategorier):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Loggoppføringer for {date} (kategorier: {categories}):",
+    ),
+    ("weekly_note", "{header} (ukenotat for {date})"),
+    ("monthly_note", "{header} (månedsnotat for {date})"),
+    ("yearly_note", "{header} (årsnotat for {date})"),
+    ("using_profile", "Bruker profilen '{profile}'"),
+];
+
+const NORWEGIAN_NYNORSK: &[(&str, &str)] = &[
+    ("time_label", "Tidspunkt"),
+    ("event_label", "Hending"),
+    ("conjunction", "og"),
+    ("logged", "Logga."),
+    ("removed", "Fjerna: {entry}"),
+    ("changed", "Endra: {old} -> {new}"),
+    ("done", "Fullført: {entry}"),
+    ("properties_set", "Sette {properties}."),
+    ("no_log_found", "Fann ingen logg for {date}"),
+    ("no_entries_for_day", "Ingen oppføringar for {date}"),
+    (
+        "no_entries_for_categories",
+        "Ingen oppføringar for dei valde kategoriane.",
+    ),
+    (
+        "no_entries_in_range",
+        "Ingen oppføringar frå {from} til {to}",
+    ),
+    (
+        "no_search_matches",
+        "Ingen oppføringar samsvarar med '{pattern}'",
+    ),
+    ("entries_for_day", "Loggoppføringar for {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Loggoppføringar for {date} (alle kategoriar):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Loggoppføringar for {date} (kategoriar: {categories}):",
+    ),
+    ("weekly_note", "{header} (vekenotat for {date})"),
+    ("monthly_note", "{header} (månadsnotat for {date})"),
+    ("yearly_note", "{header} (årsnotat for {date})"),
+    ("using_profile", "Brukar profilen '{profile}'"),
+];
+
+const DANISH: &[(&str, &str)] = &[
+    ("time_label", "Tidspunkt"),
+    ("event_label", "Begivenhed"),
+    ("conjunction", "og"),
+    ("logged", "Logget."),
+    ("removed", "Fjernet: {entry}"),
+    ("changed", "Ændret: {old} -> {new}"),
+    ("done", "Udført: {entry}"),
+    ("properties_set", "Satte {properties}."),
+    ("no_log_found", "Ingen log fundet for {date}"),
+    ("no_entries_for_day", "Ingen poster for {date}"),
+    (
+        "no_entries_for_categories",
+        "Ingen poster for de valgte kategorier.",
+    ),
+    ("no_entries_in_range", "Ingen poster fra {from} til {to}"),
+    ("no_search_matches", "Ingen poster matcher '{pattern}'"),
+    ("entries_for_day", "Logposter for {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Logposter for {date} (alle kategorier):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Logposter for {date} (kategorier: {categories}):",
+    ),
+    ("weekly_note", "{header} (ugenote for {date})"),
+    ("monthly_note", "{header} (månedsnote for {date})"),
+    ("yearly_note", "{header} (årsnote for {date})"),
+    ("using_profile", "Bruger profilen '{profile}'"),
+];
+
+const SWEDISH: &[(&str, &str)] = &[
+    ("time_label", "Tidpunkt"),
+    ("event_label", "Händelse"),
+    ("conjunction", "och"),
+    ("logged", "Loggat."),
+    ("removed", "Borttaget: {entry}"),
+    ("changed", "Ändrat: {old} -> {new}"),
+    ("done", "Klart: {entry}"),
+    ("properties_set", "Satte {properties}."),
+    ("no_log_found", "Ingen logg hittades för {date}"),
+    ("no_entries_for_day", "Inga poster för {date}"),
+    (
+        "no_entries_for_categories",
+        "Inga poster för de valda kategorierna.",
+    ),
+    ("no_entries_in_range", "Inga poster från {from} till {to}"),
+    ("no_search_matches", "Inga poster matchar '{pattern}'"),
+    ("entries_for_day", "Loggposter för {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Loggposter för {date} (alla kategorier):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Loggposter för {date} (kategorier: {categories}):",
+    ),
+    ("weekly_note", "{header} (veckoanteckning för {date})"),
+    ("monthly_note", "{header} (månadsanteckning för {date})"),
+    ("yearly_note", "{header} (årsanteckning för {date})"),
+    ("using_profile", "Använder profilen '{profile}'"),
+];
+
+const GERMAN: &[(&str, &str)] = &[
+    ("time_label", "Zeit"),
+    ("event_label", "Ereignis"),
+    ("conjunction", "und"),
+    ("logged", "Protokolliert."),
+    ("removed", "Entfernt: {entry}"),
+    ("changed", "Geändert: {old} -> {new}"),
+    ("done", "Erledigt: {entry}"),
+    ("properties_set", "{properties} gesetzt."),
+    ("no_log_found", "Kein Protokoll für {date} gefunden"),
+    ("no_entries_for_day", "Keine Einträge für {date}"),
+    (
+        "no_entries_for_categories",
+        "Keine Einträge für die angegebenen Kategorien.",
+    ),
+    ("no_entries_in_range", "Keine Einträge von {from} bis {to}"),
+    ("no_search_matches", "Keine Einträge passend zu '{pattern}'"),
+    ("entries_for_day", "Protokolleinträge für {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Protokolleinträge für {date} (alle Kategorien):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Protokolleinträge für {date} (Kategorien: {categories}):",
+    ),
+    ("weekly_note", "{header} (Wochennotiz vom {date})"),
+    ("monthly_note", "{header} (Monatsnotiz vom {date})"),
+    ("yearly_note", "{header} (Jahresnotiz vom {date})"),
+    ("using_profile", "Verwende Profil '{profile}'"),
+];
+
+const DUTCH: &[(&str, &str)] = &[
+    ("time_label", "Tijd"),
+    ("event_label", "Gebeurtenis"),
+    ("conjunction", "en"),
+    ("logged", "Gelogd."),
+    ("removed", "Verwijderd: {entry}"),
+    ("changed", "Gewijzigd: {old} -> {new}"),
+    ("done", "Afgerond: {entry}"),
+    ("properties_set", "{properties} ingesteld."),
+    ("no_log_found", "Geen log gevonden voor {date}"),
+    ("no_entries_for_day", "Geen items voor {date}"),
+    (
+        "no_entries_for_categories",
+        "Geen items voor de opgegeven categorieën.",
+    ),
+    ("no_entries_in_range", "Geen items van {from} tot {to}"),
+    ("no_search_matches", "Geen items gevonden voor '{pattern}'"),
+    ("entries_for_day", "Logitems voor {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Logitems voor {date} (alle categorieën):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Logitems voor {date} (categorieën: {categories}):",
+    ),
+    ("weekly_note", "{header} (weeknotitie van {date})"),
+    ("monthly_note", "{header} (maandnotitie van {date})"),
+    ("yearly_note", "{header} (jaarnotitie van {date})"),
+    ("using_profile", "Profiel '{profile}' wordt gebruikt"),
+];
+
+const FINNISH: &[(&str, &str)] = &[
+    ("time_label", "Aika"),
+    ("event_label", "Tapahtuma"),
+    ("conjunction", "ja"),
+    ("logged", "Kirjattu."),
+    ("removed", "Poistettu: {entry}"),
+    ("changed", "Muutettu: {old} -> {new}"),
+    ("done", "Valmis: {entry}"),
+    ("properties_set", "Asetettu {properties}."),
+    ("no_log_found", "Lokia ei löytynyt päivälle {date}"),
+    ("no_entries_for_day", "Ei merkintöjä päivälle {date}"),
+    (
+        "no_entries_for_categories",
+        "Ei merkintöjä valituissa luokissa.",
+    ),
+    ("no_entries_in_range", "Ei merkintöjä välillä {from}–{to}"),
+    ("no_search_matches", "Ei merkintöjä hakuun '{pattern}'"),
+    ("entries_for_day", "Lokimerkinnät {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Lokimerkinnät {date} (kaikki luokat):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Lokimerkinnät {date} (luokat: {categories}):",
+    ),
+    ("weekly_note", "{header} (viikkomuistiinpano {date})"),
+    ("monthly_note", "{header} (kuukausimuistiinpano {date})"),
+    ("yearly_note", "{header} (vuosimuistiinpano {date})"),
+    ("using_profile", "Käytetään profiilia '{profile}'"),
+];
+
+const FRENCH: &[(&str, &str)] = &[
+    ("time_label", "Heure"),
+    ("event_label", "Événement"),
+    ("conjunction", "et"),
+    ("logged", "Enregistré."),
+    ("removed", "Supprimé : {entry}"),
+    ("changed", "Modifié : {old} -> {new}"),
+    ("done", "Terminé : {entry}"),
+    ("properties_set", "{properties} défini."),
+    ("no_log_found", "Aucun journal trouvé pour le {date}"),
+    ("no_entries_for_day", "Aucune entrée pour le {date}"),
+    (
+        "no_entries_for_categories",
+        "Aucune entrée pour les catégories indiquées.",
+    ),
+    ("no_entries_in_range", "Aucune entrée du {from} au {to}"),
+    (
+        "no_search_matches",
+        "Aucune entrée ne correspond à '{pattern}'",
+    ),
+    ("entries_for_day", "Entrées du {date} :"),
+    (
+        "entries_for_day_all_categories",
+        "Entrées du {date} (toutes les catégories) :",
+    ),
+    (
+        "entries_for_day_categories",
+        "Entrées du {date} (catégories : {categories}) :",
+    ),
+    ("weekly_note", "{header} (note hebdomadaire du {date})"),
+    ("monthly_note", "{header} (note mensuelle du {date})"),
+    ("yearly_note", "{header} (note annuelle du {date})"),
+    ("using_profile", "Profil '{profile}' utilisé"),
+];
+
+const SPANISH: &[(&str, &str)] = &[
+    ("time_label", "Hora"),
+    ("event_label", "Evento"),
+    ("conjunction", "y"),
+    ("logged", "Registrado."),
+    ("removed", "Eliminado: {entry}"),
+    ("changed", "Modificado: {old} -> {new}"),
+    ("done", "Completado: {entry}"),
+    ("properties_set", "{properties} establecido."),
+    ("no_log_found", "No se encontró ningún registro para {date}"),
+    ("no_entries_for_day", "No hay entradas para {date}"),
+    (
+        "no_entries_for_categories",
+        "No hay entradas para las categorías indicadas.",
+    ),
+    ("no_entries_in_range", "No hay entradas del {from} al {to}"),
+    (
+        "no_search_matches",
+        "Ninguna entrada coincide con '{pattern}'",
+    ),
+    ("entries_for_day", "Entradas del {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Entradas del {date} (todas las categorías):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Entradas del {date} (categorías: {categories}):",
+    ),
+    ("weekly_note", "{header} (nota semanal del {date})"),
+    ("monthly_note", "{header} (nota mensual del {date})"),
+    ("yearly_note", "{header} (nota anual del {date})"),
+    ("using_profile", "Usando el perfil '{profile}'"),
+];
+
+const ITALIAN: &[(&str, &str)] = &[
+    ("time_label", "Ora"),
+    ("event_label", "Evento"),
+    ("conjunction", "e"),
+    ("logged", "Registrato."),
+    ("removed", "Rimosso: {entry}"),
+    ("changed", "Modificato: {old} -> {new}"),
+    ("done", "Completato: {entry}"),
+    ("properties_set", "{properties} impostato."),
+    ("no_log_found", "Nessun registro trovato per il {date}"),
+    ("no_entries_for_day", "Nessuna voce per il {date}"),
+    (
+        "no_entries_for_categories",
+        "Nessuna voce per le categorie indicate.",
+    ),
+    ("no_entries_in_range", "Nessuna voce dal {from} al {to}"),
+    (
+        "no_search_matches",
+        "Nessuna voce corrisponde a '{pattern}'",
+    ),
+    ("entries_for_day", "Voci del {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Voci del {date} (tutte le categorie):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Voci del {date} (categorie: {categories}):",
+    ),
+    ("weekly_note", "{header} (nota settimanale del {date})"),
+    ("monthly_note", "{header} (nota mensile del {date})"),
+    ("yearly_note", "{header} (nota annuale del {date})"),
+    ("using_profile", "Uso il profilo '{profile}'"),
+];
+
+const PORTUGUESE: &[(&str, &str)] = &[
+    ("time_label", "Hora"),
+    ("event_label", "Evento"),
+    ("conjunction", "e"),
+    ("logged", "Registrado."),
+    ("removed", "Removido: {entry}"),
+    ("changed", "Alterado: {old} -> {new}"),
+    ("done", "Concluído: {entry}"),
+    ("properties_set", "{properties} definido."),
+    ("no_log_found", "Nenhum registro encontrado para {date}"),
+    ("no_entries_for_day", "Nenhuma entrada para {date}"),
+    (
+        "no_entries_for_categories",
+        "Nenhuma entrada para as categorias indicadas.",
+    ),
+    ("no_entries_in_range", "Nenhuma entrada de {from} a {to}"),
+    (
+        "no_search_matches",
+        "Nenhuma entrada corresponde a '{pattern}'",
+    ),
+    ("entries_for_day", "Entradas de {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Entradas de {date} (todas as categorias):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Entradas de {date} (categorias: {categories}):",
+    ),
+    ("weekly_note", "{header} (nota semanal de {date})"),
+    ("monthly_note", "{header} (nota mensal de {date})"),
+    ("yearly_note", "{header} (nota anual de {date})"),
+    ("using_profile", "Usando o perfil '{profile}'"),
+];
+
+const POLISH: &[(&str, &str)] = &[
+    ("time_label", "Czas"),
+    ("event_label", "Zdarzenie"),
+    ("conjunction", "i"),
+    ("logged", "Zapisano."),
+    ("removed", "Usunięto: {entry}"),
+    ("changed", "Zmieniono: {old} -> {new}"),
+    ("done", "Ukończono: {entry}"),
+    ("properties_set", "Ustawiono {properties}."),
+    ("no_log_found", "Nie znaleziono dziennika dla {date}"),
+    ("no_entries_for_day", "Brak wpisów dla {date}"),
+    (
+        "no_entries_for_categories",
+        "Brak wpisów dla wybranych kategorii.",
+    ),
+    ("no_entries_in_range", "Brak wpisów od {from} do {to}"),
+    ("no_search_matches", "Brak wpisów pasujących do '{pattern}'"),
+    ("entries_for_day", "Wpisy z {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Wpisy z {date} (wszystkie kategorie):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Wpisy z {date} (kategorie: {categories}):",
+    ),
+    ("weekly_note", "{header} (notatka tygodniowa z {date})"),
+    ("monthly_note", "{header} (notatka miesięczna z {date})"),
+    ("yearly_note", "{header} (notatka roczna z {date})"),
+    ("using_profile", "Używany profil '{profile}'"),
+];
+
+const RUSSIAN: &[(&str, &str)] = &[
+    ("time_label", "Время"),
+    ("event_label", "Событие"),
+    ("conjunction", "и"),
+    ("logged", "Записано."),
+    ("removed", "Удалено: {entry}"),
+    ("changed", "Изменено: {old} -> {new}"),
+    ("done", "Выполнено: {entry}"),
+    ("properties_set", "Установлено: {properties}."),
+    ("no_log_found", "Журнал за {date} не найден"),
+    ("no_entries_for_day", "Нет записей за {date}"),
+    (
+        "no_entries_for_categories",
+        "Нет записей для указанных категорий.",
+    ),
+    ("no_entries_in_range", "Нет записей с {from} по {to}"),
+    (
+        "no_search_matches",
+        "Нет записей, соответствующих '{pattern}'",
+    ),
+    ("entries_for_day", "Записи за {date}:"),
+    (
+        "entries_for_day_all_categories",
+        "Записи за {date} (все категории):",
+    ),
+    (
+        "entries_for_day_categories",
+        "Записи за {date} (категории: {categories}):",
+    ),
+    ("weekly_note", "{header} (недельная заметка от {date})"),
+    ("monthly_note", "{header} (месячная заметка от {date})"),
+    ("yearly_note", "{header} (годовая заметка от {date})"),
+    ("using_profile", "Используется профиль '{profile}'"),
+];
+
+const JAPANESE: &[(&str, &str)] = &[
+    ("time_label", "時刻"),
+    ("event_label", "出来事"),
+    ("conjunction", "と"),
+    ("logged", "記録しました。"),
+    ("removed", "削除しました: {entry}"),
+    ("changed", "変更しました: {old} -> {new}"),
+    ("done", "完了しました: {entry}"),
+    ("properties_set", "{properties} を設定しました。"),
+    ("no_log_found", "{date} のログが見つかりません"),
+    ("no_entries_for_day", "{date} のエントリーはありません"),
+    (
+        "no_entries_for_categories",
+        "指定したカテゴリーのエントリーはありません。",
+    ),
+    (
+        "no_entries_in_range",
+        "{from} から {to} までのエントリーはありません",
+    ),
+    (
+        "no_search_matches",
+        "'{pattern}' に一致するエントリーはありません",
+    ),
+    ("entries_for_day", "{date} のログ:"),
+    (
+        "entries_for_day_all_categories",
+        "{date} のログ (すべてのカテゴリー):",
+    ),
+    (
+        "entries_for_day_categories",
+        "{date} のログ (カテゴリー: {categories}):",
+    ),
+    ("weekly_note", "{header} ({date} の週次ノート)"),
+    ("monthly_note", "{header} ({date} の月次ノート)"),
+    ("yearly_note", "{header} ({date} の年次ノート)"),
+    ("using_profile", "プロファイル '{profile}' を使用します"),
+];
+
+const KOREAN: &[(&str, &str)] = &[
+    ("time_label", "시간"),
+    ("event_label", "이벤트"),
+    ("conjunction", "와"),
+    ("logged", "기록했습니다."),
+    ("removed", "삭제됨: {entry}"),
+    ("changed", "변경됨: {old} -> {new}"),
+    ("done", "완료됨: {entry}"),
+    ("properties_set", "{properties} 설정됨."),
+    ("no_log_found", "{date}의 로그가 없습니다"),
+    ("no_entries_for_day", "{date}의 항목이 없습니다"),
+    (
+        "no_entries_for_categories",
+        "지정한 카테고리에 항목이 없습니다.",
+    ),
+    ("no_entries_in_range", "{from}부터 {to}까지 항목이 없습니다"),
+    (
+        "no_search_matches",
+        "'{pattern}'와 일치하는 항목이 없습니다",
+    ),
+    ("entries_for_day", "{date}의 로그 항목:"),
+    (
+        "entries_for_day_all_categories",
+        "{date}의 로그 항목 (모든 카테고리):",
+    ),
+    (
+        "entries_for_day_categories",
+        "{date}의 로그 항목 (카테고리: {categories}):",
+    ),
+    ("weekly_note", "{header} ({date}의 주간 노트)"),
+    ("monthly_note", "{header} ({date}의 월간 노트)"),
+    ("yearly_note", "{header} ({date}의 연간 노트)"),
+    ("using_profile", "프로필 '{profile}' 사용 중"),
+];
+
+const CHINESE: &[(&str, &str)] = &[
+    ("time_label", "时间"),
+    ("event_label", "事件"),
+    ("conjunction", "和"),
+    ("logged", "已记录。"),
+    ("removed", "已删除：{entry}"),
+    ("changed", "已修改：{old} -> {new}"),
+    ("done", "已完成：{entry}"),
+    ("properties_set", "已设置 {properties}。"),
+    ("no_log_found", "未找到 {date} 的日志"),
+    ("no_entries_for_day", "{date} 没有条目"),
+    ("no_entries_for_categories", "指定的类别中没有条目。"),
+    ("no_entries_in_range", "{from} 至 {to} 没有条目"),
+    ("no_search_matches", "没有与 '{pattern}' 匹配的条目"),
+    ("entries_for_day", "{date} 的日志条目："),
+    (
+        "entries_for_day_all_categories",
+        "{date} 的日志条目（所有类别）：",
+    ),
+    (
+        "entries_for_day_categories",
+        "{date} 的日志条目（类别：{categories}）：",
+    ),
+    ("weekly_note", "{header}（{date} 的周记）"),
+    ("monthly_note", "{header}（{date} 的月记）"),
+    ("yearly_note", "{header}（{date} 的年记）"),
+    ("using_profile", "使用配置 '{profile}'"),
+];
+
+/// The built-in bundle for a language, `None` for languages without one
+fn builtin_bundle(language: &str) -> Option<&'static [(&'static str, &'static str)]> {
+    Some(match language {
+        "en" => ENGLISH,
+        "nb" | "no" => NORWEGIAN_BOKMAL,
+        "nn" => NORWEGIAN_NYNORSK,
+        "da" => DANISH,
+        "sv" => SWEDISH,
+        "de" => GERMAN,
+        "nl" => DUTCH,
+        "fi" => FINNISH,
+        "fr" => FRENCH,
+        "es" => SPANISH,
+        "it" => ITALIAN,
+        "pt" => PORTUGUESE,
+        "pl" => POLISH,
+        "ru" => RUSSIAN,
+        "ja" => JAPANESE,
+        "ko" => KOREAN,
+        "zh" => CHINESE,
+        _ => return None,
+    })
+}
+
+/// Translated labels and messages for one locale
+#[derive(Debug, Clone, PartialEq)]
+pub struct Messages {
+    messages: HashMap<String, String>,
+}
+
+impl Messages {
+    /// The built-in translations for `locale`, with English for anything the
+    /// language does not translate
+    pub fn builtin(locale: Locale) -> Self {
+        let mut messages: HashMap<String, String> = ENGLISH
+            .iter()
+            .map(|(key, message)| (key.to_string(), message.to_string()))
+            .collect();
+        if let Some(bundle) = builtin_bundle(&language(locale)) {
+            for (key, message) in bundle {
+                messages.insert(key.to_string(), message.to_string());
+            }
+        }
+        Messages { messages }
+    }
+
+    /// The built-in translations with the user's overrides from `dir` on top:
+    /// `<language>.yaml` (`de.yaml`), then `<locale>.yaml` (`de_AT.yaml`).
+    /// Each file maps message keys to translations. Missing files are skipped.
+    pub fn load(locale: Locale, dir: Option<&Path>) -> Result<Self> {
+        let mut messages = Self::builtin(locale);
+        let Some(dir) = dir else {
+            return Ok(messages);
+        };
+        let mut names = vec![language(locale)];
+        names.push(locale.to_string());
+        names.dedup();
+        for name in names {
+            let path = dir.join(format!("{}.yaml", name));
+            if !path.is_file() {
+                continue;
+            }
+            let content = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+            let overrides: Option<HashMap<String, String>> = serde_yaml::from_str(&content)
+                .map_err(|e| {
+                    let location = e.location();
+                    Error::ConfigParse {
+                        path: path.clone(),
+                        line: location.as_ref().map(|l| l.line()),
+                        column: location.as_ref().map(|l| l.column()),
+                        message: e.to_string(),
+                    }
+                })?;
+            messages.messages.extend(overrides.unwrap_or_default());
+        }
+        Ok(messages)
+    }
+
+    /// The message for `key`, or the key itself if there is no such message
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages.get(key).map_or(key, String::as_str)
+    }
+
+    /// The message for `key` with each `{name}` replaced by its value
+    pub fn format(&self, key: &str, values: &[(&str, &dyn std::fmt::Display)]) -> String {
+        let mut message = self.get(key).to_string();
+        for (name, value) in values {
+            message = message.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        message
+    }
+}
@@ -1,8 +1,22 @@
 pub mod commands;
 pub mod config;
+pub mod document;
+pub mod entry_format;
+pub mod error;
+pub mod front_matter;
+pub mod i18n;
+pub mod locale;
+pub mod output;
+pub mod storage;
 pub mod template;
 pub mod utils;
 
 // Re-export commonly used types and functions
-pub use commands::{add, edit, list};
-pub use config::{Config, ListType, TimeFormat};
+pub use commands::{add, amend, edit, init, list, properties, search};
+pub use config::{Config, FrontMatterConfig, ListType, Period, TimeFormat};
+pub use document::{DailyLog, LogEntry, Section, TaskStatus};
+pub use entry_format::EntryFormat;
+pub use error::{Error, Result, Warning};
+pub use front_matter::FrontMatter;
+pub use i18n::Messages;
+pub use output::OutputFormat;
@@ -0,0 +1,164 @@
+use chrono::{Locale, NaiveDate};
+
+/// The locale used when none is configured, or the configured one is not known
+pub const DEFAULT_LOCALE: Locale = Locale::en_US;
+
+/// Locales used for a language on its own, where the locale is not simply the
+/// language with the same country code (like `de_DE` or `fr_FR`)
+const LANGUAGE_DEFAULTS: &[(&str, &str)] = &[
+    ("en", "en_US"),
+    ("no", "nb_NO"),
+    ("nb", "nb_NO"),
+    ("nn", "nn_NO"),
+    ("da", "da_DK"),
+    ("sv", "sv_SE"),
+    ("ja", "ja_JP"),
+    ("ko", "ko_KR"),
+    ("zh", "zh_CN"),
+    ("el", "el_GR"),
+    ("cs", "cs_CZ"),
+    ("uk", "uk_UA"),
+    ("he", "he_IL"),
+    ("hi", "hi_IN"),
+    ("ar", "ar_SA"),
+    ("ca", "ca_ES"),
+    ("et", "et_EE"),
+    ("sl", "sl_SI"),
+    ("sr", "sr_RS"),
+    ("vi", "vi_VN"),
+    ("ga", "ga_IE"),
+    ("fa", "fa_IR"),
+    ("ms", "ms_MY"),
+    ("sq", "sq_AL"),
+    ("be", "be_BY"),
+    ("ka", "ka_GE"),
+    ("kk", "kk_KZ"),
+    ("gl", "gl_ES"),
+    ("eu", "eu_ES"),
+    ("cy", "cy_GB"),
+    ("bn", "bn_BD"),
+    ("ta", "ta_IN"),
+    ("ur", "ur_PK"),
+];
+
+/// Languages that write the day of a long date as an ordinal, `16. oktober`
+const ORDINAL_DAY_LANGUAGES: &[&str] = &[
+    "nb", "nn", "da", "de", "fi", "is", "cs", "sk", "sl", "hr", "et",
+];
+
+/// Find the locale for a POSIX (`nb_NO`, `nb_NO.UTF-8`, `sr_RS@latin`) or
+/// BCP 47 (`nb-NO`, `sr-Latn-RS`) locale tag.
+///
+/// A tag with an unknown country falls back to a locale of the same language,
+/// so `sv` and `sv-FI` give `sv_SE` if needed. Returns `None` if the language
+/// itself is not known.
+pub fn parse_locale(tag: &str) -> Option<Locale> {
+    let tag = tag.trim();
+    if let Ok(locale) = tag.parse::<Locale>() {
+        return Some(locale);
+    }
+
+    // Drop the encoding of POSIX tags and split off their modifier
+    let (tag, modifier) = match tag.split_once('@') {
+        Some((tag, modifier)) => (tag, Some(modifier.to_lowercase())),
+        None => (tag, None),
+    };
+    let tag = tag.split('.').next().unwrap_or(tag);
+
+    let mut subtags = tag.split(['-', '_']).filter(|s| !s.is_empty());
+    let language = subtags.next()?.to_lowercase();
+    let mut modifier = modifier;
+    let mut region = None;
+    for subtag in subtags {
+        match subtag.len() {
+            // The Latin script is written as a modifier in POSIX tags, other
+            // scripts are the default for their locales
+            4 if subtag.eq_ignore_ascii_case("latn") => {
+                modifier.get_or_insert_with(|| "latin".to_string());
+            }
+            4 => {}
+            2 | 3 if region.is_none() => region = Some(subtag.to_uppercase()),
+            _ => {}
+        }
+    }
+
+    let mut candidates = Vec::new();
+    if let Some(region) = &region {
+        let posix = format!("{}_{}", language, region);
+        if let Some(modifier) = &modifier {
+            candidates.push(format!("{}@{}", posix, modifier));
+        }
+        candidates.push(posix);
+    }
+    if let Some((_, locale)) = LANGUAGE_DEFAULTS.iter().find(|(l, _)| *l == language) {
+        candidates.push(locale.to_string());
+    }
+    candidates.push(format!("{}_{}", language, language.to_uppercase()));
+
+    candidates
+        .iter()
+        .find_map(|candidate| candidate.parse::<Locale>().ok())
+}
+
+/// The locale for a configured locale tag, falling back to English
+pub fn resolve_locale(tag: Option<&str>) -> Locale {
+    tag.and_then(parse_locale).unwrap_or(DEFAULT_LOCALE)
+}
+
+/// The language of a locale, such as `nb` for `nb_NO`
+pub fn language(locale: Locale) -> String {
+    let name = locale.to_string();
+    name.split(['_', '@']).next().unwrap_or(&name).to_string()
+}
+
+/// The name of the weekday of `date`, such as `Friday` or `fredag`
+pub fn weekday_name(date: NaiveDate, locale: Locale) -> String {
+    format(date, "%A", locale)
+}
+
+/// The name of the month of `date`, such as `October` or `oktober`
+pub fn month_name(date: NaiveDate, locale: Locale) -> String {
+    format(date, "%B", locale)
+}
+
+/// The abbreviated name of the month of `date`, such as `Oct` or `okt.`
+pub fn short_month_name(date: NaiveDate, locale: Locale) -> String {
+    format(date, "%b", locale)
+}
+
+/// The strftime format of a long date with the weekday, such as
+/// `Friday, October 16, 2026` or `fredag 16. oktober 2026`
+pub fn long_date_format(locale: Locale) -> &'static str {
+    // The order of the short date tells whether the month or the year comes first
+    let sample = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+    let short = short_date(sample, locale);
+    let language = language(locale);
+    if ["ja", "zh", "ko"].contains(&language.as_str()) {
+        "%x %A"
+    } else if short.starts_with("10") {
+        "%A, %B %-d, %Y"
+    } else if ORDINAL_DAY_LANGUAGES.contains(&language.as_str()) {
+        "%A %-d. %B %Y"
+    } else {
+        "%A %-d %B %Y"
+    }
+}
+
+/// A long date with the weekday and the name of the month
+pub fn long_date(date: NaiveDate, locale: Locale) -> String {
+    format(date, long_date_format(locale), locale)
+}
+
+/// The locale's own short date, such as `10/16/2026` or `16.10.2026`
+pub fn short_date(date: NaiveDate, locale: Locale) -> String {
+    format(date, "%x", locale)
+}
+
+/// Format `date`, dropping the stray spaces some locales have around names
+fn format(date: NaiveDate, format: &str, locale: Locale) -> String {
+    date.format_localized(format, locale)
+        .to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,121 @@
+use crate::document::TaskStatus;
+use chrono::{NaiveDate, NaiveTime};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Output format used when listing entries
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum OutputFormat {
+    /// Markdown lines as they would appear in the note
+    #[default]
+    Markdown,
+    /// A single JSON array of entries
+    Json,
+    /// One JSON object per line
+    JsonLines,
+    /// Comma separated values with a header row
+    Csv,
+    /// Tab separated values with a header row
+    Tsv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            "json" => Ok(OutputFormat::Json),
+            "jsonl" | "json-lines" | "ndjson" => Ok(OutputFormat::JsonLines),
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Markdown => write!(f, "markdown"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::JsonLines => write!(f, "jsonl"),
+            OutputFormat::Csv => write!(f, "csv"),
+            OutputFormat::Tsv => write!(f, "tsv"),
+        }
+    }
+}
+
+/// A listed entry in machine-readable form.
+/// Times are always written as 24-hour `HH:MM:SS`, independent of `time_format`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntryRecord {
+    pub date: NaiveDate,
+    pub time: NaiveTime,
+    pub category: Option<String>,
+    pub section: String,
+    pub text: String,
+    /// Whether a task is open or done, left out of the JSON for plain entries.
+    /// Not written to CSV and TSV, which keep their columns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<TaskStatus>,
+}
+
+const COLUMNS: [&str; 5] = ["date", "time", "category", "section", "text"];
+
+/// Render records in one of the machine-readable formats.
+/// `Markdown` is not a record format and renders nothing.
+pub fn render_records(records: &[EntryRecord], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Markdown => String::new(),
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(records).expect("records serialize to JSON") + "\n"
+        }
+        OutputFormat::JsonLines => records
+            .iter()
+            .map(|r| serde_json::to_string(r).expect("records serialize to JSON") + "\n")
+            .collect(),
+        OutputFormat::Csv => render_delimited(records, ',', escape_csv),
+        OutputFormat::Tsv => render_delimited(records, '\t', escape_tsv),
+    }
+}
+
+fn render_delimited(
+    records: &[EntryRecord],
+    separator: char,
+    escape: fn(&str) -> String,
+) -> String {
+    let mut out = COLUMNS.join(&separator.to_string());
+    out.push('\n');
+    for record in records {
+        let fields = [
+            record.date.format("%Y-%m-%d").to_string(),
+            record.time.format("%H:%M:%S").to_string(),
+            record.category.clone().unwrap_or_default(),
+            record.section.clone(),
+            record.text.clone(),
+        ];
+        let escaped: Vec<String> = fields.iter().map(|f| escape(f)).collect();
+        out.push_str(&escaped.join(&separator.to_string()));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break (RFC 4180)
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escape backslashes, tabs and line breaks so every record stays on one line
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
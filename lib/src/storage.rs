@@ -0,0 +1,80 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Name of the lock file kept in the vault root
+pub const LOCK_FILE_NAME: &str = ".obsidian-logging.lock";
+
+/// An exclusive advisory lock on the vault, held until dropped.
+///
+/// Every read-modify-write of a note happens while holding this lock, so
+/// concurrent invocations never overwrite each other's entries.
+pub struct VaultLock {
+    _file: File,
+}
+
+impl VaultLock {
+    /// Block until the vault lock is acquired. The vault must exist, so that
+    /// a mistyped vault is not created.
+    pub fn acquire(config: &Config) -> Result<Self> {
+        config.ensure_vault()?;
+
+        let path = config.vault_path().join(LOCK_FILE_NAME);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| Error::io(&path, e))?;
+        file.lock().map_err(|e| Error::io(&path, e))?;
+
+        Ok(VaultLock { _file: file })
+    }
+}
+
+/// Replace the contents of `path` atomically.
+///
+/// The content is written to a temporary file in the same directory, flushed
+/// to disk and renamed over the target, so readers and crashes only ever see
+/// the old or the new note, never a truncated one.
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let temp_path = temp_path_for(path);
+    let result = write_temp_file(&temp_path, path, content)
+        .and_then(|_| fs::rename(&temp_path, path).map_err(|e| Error::io(path, e)));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_temp_file(temp_path: &Path, target: &Path, content: &str) -> Result<()> {
+    let mut file = File::create(temp_path).map_err(|e| Error::io(temp_path, e))?;
+    file.write_all(content.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| Error::io(temp_path, e))?;
+
+    // Keep the permissions of the note being replaced
+    if let Ok(metadata) = fs::metadata(target) {
+        fs::set_permissions(temp_path, metadata.permissions())
+            .map_err(|e| Error::io(temp_path, e))?;
+    }
+    Ok(())
+}
+
+/// A unique hidden sibling of `path`, so that it is skipped when scanning the vault
+fn temp_path_for(path: &Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        name,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
@@ -1,7 +1,31 @@
 use crate::config::Config;
-use chrono::{Datelike, Duration, Local, Locale, Weekday};
+use crate::error::{Error, Result, Warning};
+use crate::locale;
+use crate::utils::expand_tilde;
+use chrono::format::{DelayedFormat, Item, StrftimeItems};
+use chrono::{Datelike, Duration, Local, Locale, Months, NaiveDate, NaiveDateTime, Weekday};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt::Write;
 use std::fs::{self};
-use std::path::PathBuf;
+
+/// A placeholder expression: a name with an optional date offset and format,
+/// or the condition of an `{if ...}` block
+const EXPRESSION: &str = r"if\s+[^{}\n]+?|[A-Za-z_][A-Za-z0-9_]*(?:[+-]\d+[dwmy])?(?::[^{}\n]*)?";
+
+lazy_static! {
+    /// `{expression}`, or `{{expression}}` as in Obsidian's own templates
+    static ref PLACEHOLDER: Regex = Regex::new(&format!(
+        r"\{{\{{\s*({e})\s*\}}\}}|\{{({e})\}}",
+        e = EXPRESSION
+    ))
+    .unwrap();
+    static ref DATE_EXPRESSION: Regex = Regex::new(
+        r"^(date|today|yesterday|tomorrow|created|now)(?:([+-])(\d+)([dwmy]))?(?::(.+))?$"
+    )
+    .unwrap();
+}
 
 pub struct TemplateData {
     pub today: String,
@@ -9,59 +33,29 @@ pub struct TemplateData {
     pub tomorrow: String,
     pub weekday: String,
     pub created: String,
+    /// The date of the note
+    pub date: NaiveDate,
+    /// When the note is created
+    pub created_at: NaiveDateTime,
+    /// The locale for month and weekday names in date formats
+    pub locale: Locale,
+    /// User-defined variables from `template_variables` in the configuration
+    pub variables: HashMap<String, String>,
 }
 
 impl TemplateData {
-    fn map_locale(locale_str: &str) -> Option<Locale> {
-        match locale_str {
-            "en_US" => Some(Locale::en_US),
-            "nb_NO" => Some(Locale::nb_NO), // Norwegian Bokmål
-            "nn_NO" => Some(Locale::nn_NO), // Norwegian Nynorsk
-            "de_DE" => Some(Locale::de_DE), // German
-            "fr_FR" => Some(Locale::fr_FR), // French
-            "es_ES" => Some(Locale::es_ES), // Spanish
-            "it_IT" => Some(Locale::it_IT), // Italian
-            "ja_JP" => Some(Locale::ja_JP), // Japanese
-            "ko_KR" => Some(Locale::ko_KR), // Korean
-            "ru_RU" => Some(Locale::ru_RU), // Russian
-            "zh_CN" => Some(Locale::zh_CN), // Chinese
-            _ => Some(Locale::nb_NO),
-        }
-    }
-
-    fn get_weekday_name(weekday: Weekday, locale: Locale) -> String {
-        // Create a known date for this weekday (using 2024-01-01 as Monday)
-        let base_monday = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
-        let days_to_add = match weekday {
-            Weekday::Mon => 0,
-            Weekday::Tue => 1,
-            Weekday::Wed => 2,
-            Weekday::Thu => 3,
-            Weekday::Fri => 4,
-            Weekday::Sat => 5,
-            Weekday::Sun => 6,
-        };
-        let target_date = base_monday + Duration::days(days_to_add);
-        target_date
-            .format_localized("%A", locale)
-            .to_string()
-            .to_lowercase()
+    pub fn new(locale_str: Option<&str>) -> Self {
+        Self::for_date(Local::now().date_naive(), locale_str)
     }
 
-    pub fn new(locale_str: Option<&str>) -> Self {
+    /// Template data for the note of `today`, which need not be the current date
+    pub fn for_date(today: NaiveDate, locale_str: Option<&str>) -> Self {
         let now = Local::now();
-        let today = now.date_naive();
         let yesterday = today - Duration::days(1);
         let tomorrow = today + Duration::days(1);
 
-        // Get weekday name based on locale
-        let weekday = match locale_str {
-            Some(loc) => match Self::map_locale(loc) {
-                Some(locale) => Self::get_weekday_name(today.weekday(), locale),
-                None => Self::weekday_to_string(today.weekday()),
-            },
-            None => Self::weekday_to_string(today.weekday()),
-        };
+        let locale = locale::resolve_locale(locale_str);
+        let weekday = locale::weekday_name(today, locale).to_lowercase();
 
         Self {
             today: today.format("%Y-%m-%d").to_string(),
@@ -69,55 +63,394 @@ impl TemplateData {
             tomorrow: tomorrow.format("%Y-%m-%d").to_string(),
             weekday,
             created: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            date: today,
+            created_at: now.naive_local(),
+            locale,
+            variables: HashMap::new(),
+        }
+    }
+
+    /// Make user-defined variables available to the template
+    pub fn with_variables(mut self, variables: HashMap<String, String>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// The value of a placeholder, or a warning if it is not known
+    fn value(&self, expression: &str) -> std::result::Result<String, String> {
+        match expression {
+            "today" | "date" => return Ok(self.today.clone()),
+            "yesterday" => return Ok(self.yesterday.clone()),
+            "tomorrow" => return Ok(self.tomorrow.clone()),
+            "weekday" => return Ok(self.weekday.clone()),
+            "created" | "now" => return Ok(self.created.clone()),
+            "week" => return Ok(self.date.format("%V").to_string()),
+            "week_year" => return Ok(self.date.format("%G").to_string()),
+            "year" => return Ok(self.date.format("%Y").to_string()),
+            "month" => return Ok(self.date.format("%m").to_string()),
+            "day" => return Ok(self.date.format("%d").to_string()),
+            "quarter" => return Ok(self.date.format("%q").to_string()),
+            "month_name" => return Ok(locale::month_name(self.date, self.locale)),
+            "short_month_name" => return Ok(locale::short_month_name(self.date, self.locale)),
+            "short_weekday" => return self.format_date(self.date.into(), "%a"),
+            "long_date" => return Ok(locale::long_date(self.date, self.locale)),
+            "short_date" => return Ok(locale::short_date(self.date, self.locale)),
+            _ => {}
         }
+        if let Some(name) = expression.strip_prefix("env:") {
+            return std::env::var(name)
+                .map_err(|_| format!("environment variable '{}' is not set", name));
+        }
+        if let Some(value) = self.variables.get(expression) {
+            return Ok(value.clone());
+        }
+        if let Some(captures) = DATE_EXPRESSION.captures(expression) {
+            return self.date_value(&captures);
+        }
+        Err(format!("unknown placeholder '{{{}}}'", expression))
+    }
+
+    /// `{date+7d:%Y-%m-%d}` and similar: a date relative to the note, in a
+    /// strftime format
+    fn date_value(&self, captures: &regex::Captures) -> std::result::Result<String, String> {
+        let base = match &captures[1] {
+            "yesterday" => (self.date - Duration::days(1)).into(),
+            "tomorrow" => (self.date + Duration::days(1)).into(),
+            "created" | "now" => self.created_at,
+            _ => self.date.into(),
+        };
+        let value = match (captures.get(2), captures.get(3), captures.get(4)) {
+            (Some(sign), Some(amount), Some(unit)) => {
+                let amount: u32 = amount
+                    .as_str()
+                    .parse()
+                    .map_err(|_| format!("invalid date offset in '{}'", &captures[0]))?;
+                let forward = sign.as_str() == "+";
+                let shifted = match unit.as_str() {
+                    "d" | "w" => {
+                        let days = amount as i64 * if unit.as_str() == "w" { 7 } else { 1 };
+                        let offset = Duration::days(days);
+                        if forward {
+                            base.checked_add_signed(offset)
+                        } else {
+                            base.checked_sub_signed(offset)
+                        }
+                    }
+                    _ => {
+                        let months =
+                            Months::new(amount * if unit.as_str() == "y" { 12 } else { 1 });
+                        if forward {
+                            base.checked_add_months(months)
+                        } else {
+                            base.checked_sub_months(months)
+                        }
+                    }
+                };
+                shifted.ok_or_else(|| format!("date out of range in '{}'", &captures[0]))?
+            }
+            _ => base,
+        };
+
+        let default_format = match &captures[1] {
+            "created" | "now" => "%Y-%m-%d %H:%M:%S",
+            _ => "%Y-%m-%d",
+        };
+        let format = captures.get(5).map_or(default_format, |f| f.as_str());
+        self.format_date(value, format)
+    }
+
+    fn format_date(
+        &self,
+        value: NaiveDateTime,
+        format: &str,
+    ) -> std::result::Result<String, String> {
+        let locale = self.locale;
+        let format = match format {
+            "long" => locale::long_date_format(locale),
+            "short" => "%x",
+            format => format,
+        };
+        let items = StrftimeItems::new_with_locale(format, locale);
+        if items.clone().any(|item| item == Item::Error) {
+            return Err(format!("invalid date format '{}'", format));
+        }
+        let mut formatted = String::new();
+        write!(
+            formatted,
+            "{}",
+            DelayedFormat::new_with_locale(Some(value.date()), Some(value.time()), items, locale)
+        )
+        .map_err(|_| format!("invalid date format '{}'", format))?;
+        Ok(formatted)
     }
 
-    // Fallback English weekday names
-    fn weekday_to_string(weekday: Weekday) -> String {
-        match weekday {
-            Weekday::Mon => "monday",
-            Weekday::Tue => "tuesday",
-            Weekday::Wed => "wednesday",
-            Weekday::Thu => "thursday",
-            Weekday::Fri => "friday",
-            Weekday::Sat => "saturday",
-            Weekday::Sun => "sunday",
-        }
-        .to_string()
+    /// Whether the condition of an `{if ...}` block holds for the note
+    fn condition(&self, condition: &str) -> std::result::Result<bool, String> {
+        if let Some(negated) = condition.strip_prefix("not ") {
+            return self.condition(negated.trim()).map(|holds| !holds);
+        }
+        let weekday = self.date.weekday();
+        let weekend = matches!(weekday, Weekday::Sat | Weekday::Sun);
+        match condition {
+            "weekend" => return Ok(weekend),
+            "workday" => return Ok(!weekend),
+            _ => {}
+        }
+        if let Ok(day) = condition.parse::<Weekday>() {
+            return Ok(day == weekday);
+        }
+        if let Some(name) = condition.strip_prefix("env:") {
+            return Ok(std::env::var(name).is_ok_and(|v| !v.is_empty()));
+        }
+        if let Some(value) = self.variables.get(condition) {
+            return Ok(!value.is_empty());
+        }
+        Err(format!("unknown condition '{{if {}}}'", condition))
     }
 }
 
-pub fn process_template(template_path: &str, data: &TemplateData) -> String {
-    // Expand ~ to home directory if present
-    let expanded_path = if template_path.starts_with("~") {
-        if let Ok(home) = std::env::var("HOME") {
-            let path = template_path.strip_prefix("~").unwrap_or(template_path);
-            PathBuf::from(home).join(path.strip_prefix("/").unwrap_or(path))
+/// Substitute the placeholders and evaluate the `{if ...}` blocks of a template.
+/// Returns the rendered template and a warning for every placeholder that could
+/// not be filled in. Those placeholders are kept as they are, so that text such
+/// as Obsidian's own `{{title}}` survives.
+///
+/// Placeholders are written `{name}` or `{{name}}`:
+/// - `{today}`, `{yesterday}`, `{tomorrow}`, `{weekday}` and `{created}`
+/// - `{week}`, `{week_year}`, `{year}`, `{month}`, `{day}`, `{quarter}` and `{month_name}`
+/// - `{date:<strftime>}`, `{date+7d}`, `{date-1m:%B}`, with units d, w, m and y;
+///   `today`, `yesterday`, `tomorrow` and `created` work the same way
+/// - `{env:NAME}` for environment variables, and the names of `template_variables`
+///
+/// `{if weekend}...{else}...{end}` keeps one of its branches. Conditions are
+/// `weekend`, `workday`, a weekday name, `env:NAME` or a template variable (set
+/// and not empty), optionally prefixed with `not`.
+pub fn render_template(template: &str, data: &TemplateData) -> (String, Vec<String>) {
+    let mut warnings = Vec::new();
+    let nodes = parse_template(template, &mut warnings);
+    let mut output = String::new();
+    render_nodes(&nodes, data, &mut output, &mut warnings);
+    (output, warnings)
+}
+
+enum Node<'a> {
+    Text(&'a str),
+    /// A placeholder: its expression and the text it was written as
+    Value {
+        expression: &'a str,
+        text: &'a str,
+    },
+    If {
+        condition: &'a str,
+        then: Vec<Node<'a>>,
+        otherwise: Vec<Node<'a>>,
+    },
+}
+
+/// An `{if ...}` block that has not seen its `{end}` yet
+struct OpenBlock<'a> {
+    condition: &'a str,
+    /// The nodes before the block
+    outer: Vec<Node<'a>>,
+    /// The first branch, once `{else}` has been seen
+    then: Vec<Node<'a>>,
+    in_else: bool,
+}
+
+fn parse_template<'a>(template: &'a str, warnings: &mut Vec<String>) -> Vec<Node<'a>> {
+    let mut open: Vec<OpenBlock<'a>> = Vec::new();
+    let mut current: Vec<Node<'a>> = Vec::new();
+    let mut last = 0;
+
+    for captures in PLACEHOLDER.captures_iter(template) {
+        let whole = captures.get(0).unwrap();
+        let expression = captures.get(1).or(captures.get(2)).unwrap().as_str();
+        let condition = expression
+            .strip_prefix("if")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .map(str::trim);
+        let is_block = condition.is_some() || expression == "else" || expression == "end";
+
+        // A block tag alone on its line takes the whole line with it
+        let (mut start, mut end) = (whole.start(), whole.end());
+        if is_block {
+            let line_start = template[..start].rfind('\n').map_or(0, |i| i + 1);
+            let rest = &template[end..];
+            let line_end = if rest.starts_with("\r\n") {
+                Some(2)
+            } else if rest.starts_with('\n') || rest.is_empty() {
+                Some(rest.len().min(1))
+            } else {
+                None
+            };
+            if let (true, Some(newline)) = (template[line_start..start].trim().is_empty(), line_end)
+            {
+                start = line_start;
+                end += newline;
+            }
+        }
+        if start > last {
+            current.push(Node::Text(&template[last..start]));
+        }
+        last = end;
+
+        if let Some(condition) = condition {
+            open.push(OpenBlock {
+                condition,
+                outer: std::mem::take(&mut current),
+                then: Vec::new(),
+                in_else: false,
+            });
+        } else if expression == "else" {
+            match open.last_mut() {
+                Some(block) if !block.in_else => {
+                    block.then = std::mem::take(&mut current);
+                    block.in_else = true;
+                }
+                _ => {
+                    warnings.push("{else} without {if ...}".to_string());
+                    current.push(Node::Text(whole.as_str()));
+                }
+            }
+        } else if expression == "end" {
+            match open.pop() {
+                Some(block) => current = close_block(block, current),
+                None => {
+                    warnings.push("{end} without {if ...}".to_string());
+                    current.push(Node::Text(whole.as_str()));
+                }
+            }
         } else {
-            PathBuf::from(template_path)
+            current.push(Node::Value {
+                expression,
+                text: whole.as_str(),
+            });
         }
+    }
+    if last < template.len() {
+        current.push(Node::Text(&template[last..]));
+    }
+
+    while let Some(block) = open.pop() {
+        warnings.push(format!("{{if {}}} without {{end}}", block.condition));
+        current = close_block(block, current);
+    }
+    current
+}
+
+/// Finish an `{if}` block whose last branch is `current`, returning the
+/// enclosing nodes with the block appended
+fn close_block<'a>(block: OpenBlock<'a>, current: Vec<Node<'a>>) -> Vec<Node<'a>> {
+    let (then, otherwise) = if block.in_else {
+        (block.then, current)
     } else {
-        PathBuf::from(template_path)
+        (current, Vec::new())
     };
+    let mut enclosing = block.outer;
+    enclosing.push(Node::If {
+        condition: block.condition,
+        then,
+        otherwise,
+    });
+    enclosing
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    data: &TemplateData,
+    output: &mut String,
+    warnings: &mut Vec<String>,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Value { expression, text } => match data.value(expression) {
+                Ok(value) => output.push_str(&value),
+                Err(warning) => {
+                    output.push_str(text);
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+            },
+            Node::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let holds = data.condition(condition).unwrap_or_else(|warning| {
+                    warnings.push(warning);
+                    false
+                });
+                let branch = if holds { then } else { otherwise };
+                render_nodes(branch, data, output, warnings);
+            }
+        }
+    }
+}
+
+/// Read the template at `template_path` and render it with `data`, returning a
+/// warning for each placeholder that could not be filled in.
+/// A missing template file falls back to an empty log section.
+pub fn process_template(
+    template_path: &str,
+    data: &TemplateData,
+) -> Result<(String, Vec<Warning>)> {
+    let expanded_path = expand_tilde(template_path);
 
     let template = match fs::read_to_string(&expanded_path) {
         Ok(content) => content,
-        Err(_) => String::from("## 🕗\n\n"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::from("## 🕗\n\n"),
+        Err(e) => {
+            return Err(Error::Template {
+                path: expanded_path,
+                source: e,
+            })
+        }
     };
 
-    template
-        .replace("{today}", &data.today)
-        .replace("{yesterday}", &data.yesterday)
-        .replace("{tomorrow}", &data.tomorrow)
-        .replace("{weekday}", &data.weekday)
-        .replace("{created}", &data.created)
+    let (content, warnings) = render_template(&template, data);
+    let warnings = warnings
+        .into_iter()
+        .map(|warning| Warning::new(&expanded_path, warning))
+        .collect();
+    Ok((content, warnings))
+}
+
+pub fn get_template_content(config: &Config) -> Result<(String, Vec<Warning>)> {
+    get_template_content_for_date(config, Local::now().date_naive())
 }
 
-pub fn get_template_content(config: &Config) -> String {
-    let template_data = TemplateData::new(config.locale.as_deref());
+/// Template content for the note of `date`
+pub fn get_template_content_for_date(
+    config: &Config,
+    date: NaiveDate,
+) -> Result<(String, Vec<Warning>)> {
+    let template_data = TemplateData::for_date(date, config.locale.as_deref())
+        .with_variables(config.template_variables.clone());
 
     match &config.template_path {
         Some(path) => process_template(path, &template_data),
-        None => String::from("## 🕗\n\n"),
+        None => Ok((String::from("## 🕗\n\n"), Vec::new())),
+    }
+}
+
+/// Template content for the note that `category` is logged to on `date`.
+/// Periodic notes use their own template, filled in for the first day of
+/// the period, and start out empty without one.
+pub fn get_template_content_for_category(
+    config: &Config,
+    category: Option<&str>,
+    date: NaiveDate,
+) -> Result<(String, Vec<Warning>)> {
+    match config.periodic_note_for_category(category) {
+        Some((period, note)) => match &note.template_path {
+            Some(path) => process_template(
+                path,
+                &TemplateData::for_date(period.start(date), config.locale.as_deref())
+                    .with_variables(config.template_variables.clone()),
+            ),
+            None => Ok((String::new(), Vec::new())),
+        },
+        None => get_template_content_for_date(config, date),
     }
 }
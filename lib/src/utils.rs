@@ -1,12 +1,30 @@
-use crate::config::{Config, ListType, TimeFormat};
-use chrono::{NaiveDate, NaiveTime, Timelike};
+use crate::config::{Config, ListType, Period, TimeFormat};
+use crate::document::DailyLog;
+use crate::error::{Error, Result, Warning};
+use crate::locale;
+use chrono::format::{Fixed, Item, Numeric, Pad, Parsed, StrftimeItems};
+use chrono::{Datelike, Duration, Locale, NaiveDate, NaiveTime, Timelike, Weekday};
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::fmt::Write;
+use std::fs;
 use std::path::PathBuf;
 
 lazy_static! {
-    static ref TIME_PATTERN: Regex =
-        Regex::new(r"^(?:[-*]\s*)?(\d{2}:\d{2}(?::\d{2})?(?:\s*[AaPp][Mm])?)\s*(.+)$").unwrap();
+    static ref PATH_TOKEN: Regex = Regex::new(
+        r"\{(year|month|date|day|day_of_year|week|week_year|quarter|month_name|short_month_name|weekday|short_weekday|long_date|short_date|date:[^}]+)\}"
+    )
+    .unwrap();
+}
+
+/// Expand a leading `~` to the home directory, if `HOME` is set
+pub fn expand_tilde(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix('~') {
+        if let Ok(home) = std::env::var("HOME") {
+            return PathBuf::from(home).join(rest.strip_prefix('/').unwrap_or(rest));
+        }
+    }
+    PathBuf::from(path)
 }
 
 /// Format time according to the specified format (12 or 24 hour)
@@ -28,6 +46,25 @@ pub fn format_time(time: NaiveTime, format: &TimeFormat) -> String {
     }
 }
 
+/// A duration as hours and minutes, `1:30`. An end before the start is on the
+/// next day.
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().rem_euclid(24 * 60);
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Clean up entry text: trailing whitespace is removed from every line, and
+/// leading and trailing blank lines are dropped. Line breaks inside are kept.
+pub fn normalize_entry_text(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().map(|line| line.trim_end()).collect();
+    let start = lines.iter().position(|l| !l.trim().is_empty());
+    let end = lines.iter().rposition(|l| !l.trim().is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n").trim_start().to_string(),
+        _ => String::new(),
+    }
+}
+
 /// Parse time string in either 12 or 24 hour format
 /// Supports both HH:MM and HH:MM:SS formats. If seconds are not provided, defaults to 00.
 pub fn parse_time(time_str: &str) -> Option<NaiveTime> {
@@ -94,265 +131,395 @@ pub fn parse_time(time_str: &str) -> Option<NaiveTime> {
     None
 }
 
-/// Build file path for given date and format string from configuration yaml
-/// Supported tokens: {year}, {month}, {date}
-pub fn get_log_path_for_date(date: NaiveDate, config: &Config) -> PathBuf {
-    let mut path = PathBuf::from(&config.vault);
+/// Parse a date given on the command line, relative to `today`.
+/// Accepts `YYYY-MM-DD`, `today`, `yesterday`, `tomorrow`, and `<n>d` or `<n>w`
+/// for the date `n` days or weeks ago.
+pub fn parse_date_expression(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    match input.as_str() {
+        "today" => return Some(today),
+        "yesterday" => return Some(today - Duration::days(1)),
+        "tomorrow" => return Some(today + Duration::days(1)),
+        _ => {}
+    }
 
-    let year = date.format("%Y").to_string();
-    let month = date.format("%m").to_string();
-    let date_str = date.format("%Y-%m-%d").to_string();
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Some(date);
+    }
 
-    let file_path = config
-        .file_path_format
-        .replace("{year}", &year)
-        .replace("{month}", &month)
-        .replace("{date}", &date_str);
+    let (amount, unit_days) = if let Some(days) = input.strip_suffix('d') {
+        (days, 1)
+    } else if let Some(weeks) = input.strip_suffix('w') {
+        (weeks, 7)
+    } else {
+        return None;
+    };
+    let amount: i64 = amount.parse().ok()?;
+    Some(today - Duration::days(amount * unit_days))
+}
 
+/// Build file path for given date and format string from configuration yaml
+///
+/// Supported tokens:
+/// - `{year}`, `{month}` and `{date}` (2024, 03 and 2024-03-15)
+/// - `{day}` and `{day_of_year}` (15 and 075)
+/// - `{week}` and `{week_year}`, the ISO 8601 week and the year it belongs to
+/// - `{quarter}` (1 to 4)
+/// - `{month_name}`, `{short_month_name}`, `{weekday}` and `{short_weekday}`,
+///   in the configured locale
+/// - `{long_date}`, the date written the way the locale does
+/// - `{short_date}`, the same as `{date}`
+/// - `{date:<strftime>}`, any strftime format such as `{date:%Y/W%V}`
+///
+/// A format that chrono cannot render is a configuration error.
+pub fn get_log_path_for_date(date: NaiveDate, config: &Config) -> Result<PathBuf> {
+    let mut path = config.vault_path();
+    let format = path_strftime(&config.file_path_format, config.resolved_locale());
+
+    let mut file_path = String::new();
+    write!(
+        file_path,
+        "{}",
+        date.format_localized(&format, config.resolved_locale())
+    )
+    .map_err(|_| Error::ConfigInvalid {
+        path: None,
+        problems: vec![format!(
+            "file_path_format '{}' contains an invalid date format",
+            config.file_path_format
+        )],
+    })?;
     path.push(file_path);
-    path
+    Ok(path)
 }
 
-/// Format a table row with given widths for timestamp and entry columns
-fn format_table_row(timestamp: &str, entry: &str, time_width: usize, entry_width: usize) -> String {
-    format!(
-        "| {:<width_t$} | {:<width_e$} |",
-        timestamp,
-        entry,
-        width_t = time_width,
-        width_e = entry_width
-    )
+/// Recognise the date of a daily note from its path relative to the vault.
+/// This is the reverse of [`get_log_path_for_date`]; paths that do not match
+/// `file_path_format`, or whose date cannot be determined, return `None`.
+pub fn parse_log_path(relative_path: &str, config: &Config) -> Option<NaiveDate> {
+    LogPathMatcher::new(config)?.parse(relative_path)
 }
 
-/// Format a table separator line with given column widths
-fn format_table_separator(time_width: usize, entry_width: usize) -> String {
-    format!(
-        "|{}|{}|",
-        "-".repeat(time_width + 2),
-        "-".repeat(entry_width + 2)
-    )
+/// The pattern of the paths of `file_path_format`, built once to recognise the
+/// dates of many notes
+struct LogPathMatcher<'a> {
+    config: &'a Config,
+    pattern: Regex,
+    /// The strftime fields captured by the groups of the pattern, in order
+    fields: Vec<Item<'static>>,
 }
 
-/// Parse an entry to extract timestamp and content
-fn parse_entry(entry: &str) -> (String, String) {
-    if entry.starts_with('|') {
-        // Parse table format
-        let parts: Vec<&str> = entry.split('|').collect();
-        if parts.len() >= 4 {
-            return (parts[1].trim().to_string(), parts[2].trim().to_string());
-        }
-    } else if entry.starts_with(['*', '-']) {
-        // Parse bullet format - handle both 24-hour and 12-hour time formats
-        let content = entry.trim_start_matches(['-', '*', ' ']);
-
-        // Try to find a valid time pattern at the beginning
-        let time_patterns = [
-            // 24-hour format: HH:MM:SS
-            r"^(\d{1,2}:\d{2}:\d{2})\s+(.+)$",
-            // 24-hour format: HH:MM (backward compatibility)
-            r"^(\d{1,2}:\d{2})\s+(.+)$",
-            // 12-hour format: HH:MM:SS AM/PM
-            r"^(\d{1,2}:\d{2}:\d{2}\s+[AaPp][Mm])\s+(.+)$",
-            // 12-hour format: HH:MM AM/PM (backward compatibility)
-            r"^(\d{1,2}:\d{2}\s+[AaPp][Mm])\s+(.+)$",
-        ];
-
-        for pattern in &time_patterns {
-            if let Ok(regex) = Regex::new(pattern) {
-                if let Some(captures) = regex.captures(content) {
-                    let time = captures.get(1).unwrap().as_str().trim();
-                    let entry_text = captures.get(2).unwrap().as_str().trim();
-                    return (time.to_string(), entry_text.to_string());
+impl<'a> LogPathMatcher<'a> {
+    /// `None` if `file_path_format` is not a valid date format
+    fn new(config: &'a Config) -> Option<Self> {
+        let locale = config.resolved_locale();
+        let format = path_strftime(&config.file_path_format.replace('\\', "/"), locale);
+
+        let mut pattern = String::from("^");
+        let mut fields = Vec::new();
+        for item in StrftimeItems::new_with_locale(&format, locale) {
+            match &item {
+                Item::Literal(text) | Item::Space(text) => pattern.push_str(&regex::escape(text)),
+                Item::OwnedLiteral(text) | Item::OwnedSpace(text) => {
+                    pattern.push_str(&regex::escape(text))
                 }
+                Item::Numeric(numeric, pad) => pattern.push_str(&numeric_pattern(numeric, *pad)),
+                Item::Fixed(_) => pattern.push_str(r"([^/]+?)"),
+                Item::Error => return None,
+            }
+            if matches!(item, Item::Numeric(..) | Item::Fixed(_)) {
+                fields.push(item.to_owned());
             }
         }
+        pattern.push('$');
 
-        // Fallback to original behavior for backward compatibility
-        if let Some(space_pos) = content.find(' ') {
-            if let Some(second_space) = content[space_pos + 1..].find(' ') {
-                return (
-                    content[..space_pos + 1 + second_space].trim().to_string(),
-                    content[space_pos + 1 + second_space + 1..]
-                        .trim()
-                        .to_string(),
-                );
-            }
+        Some(LogPathMatcher {
+            config,
+            pattern: Regex::new(&pattern).ok()?,
+            fields,
+        })
+    }
+
+    /// The date of the note at `relative_path`, see [`parse_log_path`]
+    fn parse(&self, relative_path: &str) -> Option<NaiveDate> {
+        let locale = self.config.resolved_locale();
+        let relative_path = relative_path.replace('\\', "/");
+        let captures = self.pattern.captures(&relative_path)?;
+        let mut parsed = Parsed::new();
+        for (item, value) in self.fields.iter().zip(captures.iter().skip(1)) {
+            set_date_field(&mut parsed, item, value?.as_str().trim(), locale)?;
         }
+        let date = first_matching_date(&parsed)?;
+
+        // All other tokens must agree with the date
+        let expected = get_log_path_for_date(date, self.config).ok()?;
+        let expected = expected.strip_prefix(self.config.vault_path()).ok()?;
+        (expected.to_string_lossy().replace('\\', "/") == relative_path).then_some(date)
     }
-    (String::new(), String::new())
 }
 
-/// Extract log entries from the log section
-/// Returns ( content before log section, content after log section, list of log entries, and detected list type)
-/// Section heading retrieved from yaml config
-pub fn extract_log_entries(
-    content: &str,
-    section_header: &str,
-    list_type: &ListType,
+/// The path of the note that `category` is logged to for `date`: the daily note,
+/// or the weekly, monthly or yearly note configured for the category
+pub fn get_log_path_for_category(
+    date: NaiveDate,
     config: &Config,
-    include_header: bool,
-) -> (String, String, Vec<String>, ListType) {
-    let mut before = String::new();
-    let mut after = String::new();
-    let mut entries = Vec::new();
-    let mut found_type = list_type.clone();
-    let mut in_section = false;
-    let mut found_section = false;
-
-    let lines = content.lines().peekable();
-    for line in lines {
-        if line.starts_with(section_header) {
-            found_section = true;
-            in_section = true;
-            before = before.trim_end().to_string() + "\n\n";
-            continue;
-        }
-
-        if in_section {
-            if line.starts_with("##") {
-                in_section = false;
-                after = line.to_string();
-                continue;
-            }
+    category: Option<&str>,
+) -> Result<PathBuf> {
+    get_log_path_for_date(date, &config.note_config(category))
+}
 
-            let trimmed = line.trim();
-            if !trimmed.is_empty() {
-                if trimmed.starts_with('|') {
-                    found_type = ListType::Table;
-                } else if trimmed.starts_with(['*', '-']) {
-                    found_type = ListType::Bullet;
-                }
+/// Check that `file_path_format` can be rendered, and that each `period` (day,
+/// week, month or year) gets its own note, whose date can be recognised from
+/// its path again
+pub fn validate_file_path_format(
+    config: &Config,
+    period: Period,
+) -> std::result::Result<(), String> {
+    let locale = config.resolved_locale();
+    let format = path_strftime(&config.file_path_format, locale);
+    if StrftimeItems::new_with_locale(&format, locale).any(|i| i == Item::Error) {
+        return Err(format!(
+            "file_path_format '{}' contains an invalid date format",
+            config.file_path_format
+        ));
+    }
 
-                // Skip table separator and header rows
-                if !trimmed.contains("---")
-                    && trimmed != format!("| {} | {} |", config.time_label, config.event_label)
-                {
-                    entries.push(line.to_string());
-                }
-            }
-        } else if !found_section {
-            before.push_str(line);
-            before.push('\n');
-        } else if !line.is_empty() {
-            after.push('\n');
-            after.push_str(line);
-        }
+    let samples = [(2024, 1, 1), (2024, 2, 29), (2026, 10, 16), (2027, 1, 3)];
+    let identifies_period = samples.iter().all(|&(year, month, day)| {
+        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let start = period.start(date);
+        let Ok(path) = get_log_path_for_date(start, config) else {
+            return false;
+        };
+        let parsed = path
+            .strip_prefix(config.vault_path())
+            .ok()
+            .and_then(|relative| parse_log_path(&relative.to_string_lossy(), config));
+        parsed == Some(start) && get_log_path_for_date(date, config).ok() == Some(path)
+    });
+    if identifies_period {
+        Ok(())
+    } else {
+        let unit = match period {
+            Period::Daily => "day",
+            Period::Weekly => "week",
+            Period::Monthly => "month",
+            Period::Yearly => "year",
+        };
+        Err(format!(
+            "file_path_format '{}' does not identify the {} of the note",
+            config.file_path_format, unit
+        ))
     }
+}
 
-    // Convert entries if needed
-    if found_type != *list_type {
-        let mut converted_entries = Vec::new();
-
-        if *list_type == ListType::Table {
-            // Convert from bullet to table
-            let mut max_time_width = config.time_label.len();
-            let mut max_entry_width = config.event_label.len();
-
-            // First pass: calculate widths
-            for entry in &entries {
-                let (time, text) = parse_entry(entry);
-                // Parse and reformat time according to config
-                let formatted_time = if let Some(parsed_time) = parse_time(&time) {
-                    format_time(parsed_time, &config.time_format)
-                } else {
-                    time
-                };
-                max_time_width = max_time_width.max(formatted_time.len());
-                max_entry_width = max_entry_width.max(text.len());
-            }
+/// The date given by the parsed fields. When they name a week, month or
+/// year rather than a day, its first day is used.
+fn first_matching_date(parsed: &Parsed) -> Option<NaiveDate> {
+    let mut candidates = vec![parsed.clone()];
+    let mut monday = parsed.clone();
+    candidates.extend(monday.set_weekday(Weekday::Mon).ok().map(|_| monday));
+    let mut first_of_month = parsed.clone();
+    candidates.extend(first_of_month.set_day(1).ok().map(|_| first_of_month));
+    let mut first_of_year = parsed.clone();
+    candidates.extend(first_of_year.set_ordinal(1).ok().map(|_| first_of_year));
+
+    candidates
+        .iter()
+        .find_map(|candidate| candidate.to_naive_date().ok())
+}
 
-            // Add header only if include_header is true
-            if include_header {
-                converted_entries.push(format_table_row(
-                    &config.time_label,
-                    &config.event_label,
-                    max_time_width,
-                    max_entry_width,
-                ));
-                converted_entries.push(format_table_separator(max_time_width, max_entry_width));
-            }
+/// The strftime format for `file_path_format`, with its tokens replaced
+fn path_strftime(format: &str, locale: Locale) -> String {
+    let mut strftime = String::new();
+    let mut last = 0;
+    for m in PATH_TOKEN.find_iter(format) {
+        strftime.push_str(&format[last..m.start()].replace('%', "%%"));
+        let token = &format[m.start() + 1..m.end() - 1];
+        strftime.push_str(match token {
+            "year" => "%Y",
+            "month" => "%m",
+            "date" => "%Y-%m-%d",
+            "day" => "%d",
+            "day_of_year" => "%j",
+            "week" => "%V",
+            "week_year" => "%G",
+            "quarter" => "%q",
+            "month_name" => "%B",
+            "short_month_name" => "%b",
+            "weekday" => "%A",
+            "short_weekday" => "%a",
+            "long_date" => locale::long_date_format(locale),
+            // The locale's short date can contain `/`, which would add directories
+            "short_date" => "%Y-%m-%d",
+            _ => &token["date:".len()..],
+        });
+        last = m.end();
+    }
+    strftime.push_str(&format[last..].replace('%', "%%"));
+    strftime
+}
 
-            // Second pass: format entries
-            for entry in entries {
-                let (time, text) = parse_entry(&entry);
-                // Parse and reformat time according to config
-                let formatted_time = if let Some(parsed_time) = parse_time(&time) {
-                    format_time(parsed_time, &config.time_format)
-                } else {
-                    time
-                };
-                converted_entries.push(format_table_row(
-                    &formatted_time,
-                    &text,
-                    max_time_width,
-                    max_entry_width,
-                ));
-            }
-        } else {
-            // Convert from table to bullet
-            // Add table header as a comment only if include_header is true
-            if include_header {
-                converted_entries.push(format!(
-                    "<!-- {} | {} -->",
-                    config.time_label, config.event_label
-                ));
-            }
+/// A capturing regex for a numeric strftime field
+fn numeric_pattern(numeric: &Numeric, pad: Pad) -> String {
+    let width = match numeric {
+        Numeric::Year | Numeric::IsoYear => 4,
+        Numeric::Ordinal => 3,
+        Numeric::Quarter | Numeric::WeekdayFromMon | Numeric::NumDaysFromSun => 1,
+        _ => 2,
+    };
+    match pad {
+        Pad::Zero => format!(r"(\d{{{}}})", width),
+        Pad::Space => format!(r"( *\d{{1,{}}})", width),
+        Pad::None => format!(r"(\d{{1,{}}})", width),
+    }
+}
 
-            for entry in entries {
-                let (time, text) = parse_entry(&entry);
-                if !time.is_empty() && !text.is_empty() {
-                    // Parse and reformat time according to config
-                    let formatted_time = if let Some(parsed_time) = parse_time(&time) {
-                        format_time(parsed_time, &config.time_format)
-                    } else {
-                        time
-                    };
-                    converted_entries.push(format!("- {} {}", formatted_time, text));
-                }
-            }
-        }
+/// Record the value of one strftime field. Fields that do not help to find the
+/// date are skipped, since the path is compared with the expected one afterwards.
+fn set_date_field(parsed: &mut Parsed, item: &Item, value: &str, locale: Locale) -> Option<()> {
+    let number = || value.parse::<i64>().ok();
+    let result = match item {
+        Item::Numeric(Numeric::Year, _) => parsed.set_year(number()?),
+        Item::Numeric(Numeric::IsoYear, _) => parsed.set_isoyear(number()?),
+        Item::Numeric(Numeric::YearMod100, _) => parsed.set_year_mod_100(number()?),
+        Item::Numeric(Numeric::Month, _) => parsed.set_month(number()?),
+        Item::Numeric(Numeric::Day, _) => parsed.set_day(number()?),
+        Item::Numeric(Numeric::Ordinal, _) => parsed.set_ordinal(number()?),
+        Item::Numeric(Numeric::IsoWeek, _) => parsed.set_isoweek(number()?),
+        Item::Fixed(Fixed::LongMonthName) => parsed.set_month(month_number(value, "%B", locale)?),
+        Item::Fixed(Fixed::ShortMonthName) => parsed.set_month(month_number(value, "%b", locale)?),
+        Item::Fixed(Fixed::LongWeekdayName) => parsed.set_weekday(weekday(value, "%A", locale)?),
+        Item::Fixed(Fixed::ShortWeekdayName) => parsed.set_weekday(weekday(value, "%a", locale)?),
+        _ => Ok(()),
+    };
+    result.ok()
+}
 
-        entries = converted_entries;
-    } else if *list_type == ListType::Table && found_type == ListType::Table && include_header {
-        // Format hasn't changed, but ensure table format has proper header
-        // Rebuild table with proper header and separator
-        let mut max_time_width = config.time_label.len();
-        let mut max_entry_width = config.event_label.len();
-
-        // First pass: calculate widths from existing entries
-        for entry in &entries {
-            let (time, text) = parse_entry(entry);
-            max_time_width = max_time_width.max(time.len());
-            max_entry_width = max_entry_width.max(text.len());
-        }
+/// The month (1-12) whose localized name in `format` is `name`
+fn month_number(name: &str, format: &str, locale: Locale) -> Option<i64> {
+    (1..=12).find_map(|month| {
+        let date = NaiveDate::from_ymd_opt(2024, month, 1)?;
+        (date.format_localized(format, locale).to_string() == name).then_some(month as i64)
+    })
+}
 
-        // Rebuild table with header
-        let mut rebuilt_entries = Vec::new();
-        rebuilt_entries.push(format_table_row(
-            &config.time_label,
-            &config.event_label,
-            max_time_width,
-            max_entry_width,
-        ));
-        rebuilt_entries.push(format_table_separator(max_time_width, max_entry_width));
+/// The weekday whose localized name in `format` is `name`
+fn weekday(name: &str, format: &str, locale: Locale) -> Option<Weekday> {
+    // 2024-01-01 is a Monday
+    let monday = NaiveDate::from_ymd_opt(2024, 1, 1)?;
+    (0..7)
+        .map(|offset| monday + Duration::days(offset))
+        .find(|date| date.format_localized(format, locale).to_string() == name)
+        .map(|date| date.weekday())
+}
 
-        // Add data rows
+/// A note found in the vault, with the date of its day or the first day of its period
+pub type LogFile = (NaiveDate, PathBuf);
+
+/// Find all notes in the vault that match `file_path_format` or the path of a
+/// weekly, monthly or yearly note, sorted by date. Periodic notes are dated by
+/// the first day of their period. Folders and files that cannot be read are
+/// skipped and returned as warnings; only an unreadable vault is an error.
+pub fn find_log_files(config: &Config) -> Result<(Vec<LogFile>, Vec<Warning>)> {
+    let note_configs = config.note_configs();
+    let matchers: Vec<LogPathMatcher> = note_configs
+        .iter()
+        .filter_map(|note_config| LogPathMatcher::new(note_config))
+        .collect();
+    let vault = config.vault_path();
+    let mut found = Vec::new();
+    let mut warnings = Vec::new();
+    let mut pending = vec![vault.clone()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if dir == vault => return Err(Error::io(&dir, e)),
+            Err(e) => {
+                // One unreadable folder does not hide the notes in the others
+                warnings.push(Warning::new(&dir, format!("skipped: {}", e)));
+                continue;
+            }
+        };
         for entry in entries {
-            let (time, text) = parse_entry(&entry);
-            if !time.is_empty() && !text.is_empty() {
-                rebuilt_entries.push(format_table_row(
-                    &time,
-                    &text,
-                    max_time_width,
-                    max_entry_width,
-                ));
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    warnings.push(Warning::new(&dir, format!("skipped an entry: {}", e)));
+                    continue;
+                }
+            };
+            let hidden = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if hidden {
+                // Skip .obsidian, .trash and similar
+                continue;
+            }
+            // Symlinked directories are not followed, so a link cycle cannot
+            // make the walk loop
+            let metadata = match fs::symlink_metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    // Such as a file deleted during the walk
+                    warnings.push(Warning::new(&path, format!("skipped: {}", e)));
+                    continue;
+                }
+            };
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if metadata.is_symlink() && path.is_dir() {
+                continue;
+            } else if let Ok(relative) = path.strip_prefix(&vault) {
+                let relative = relative.to_string_lossy();
+                if let Some(date) = matchers.iter().find_map(|matcher| matcher.parse(&relative)) {
+                    found.push((date, path));
+                }
             }
         }
-
-        entries = rebuilt_entries;
-        // If include_header is false, keep original entries as-is
-        // For bullet format, entries are already in the correct format
     }
 
+    found.sort();
+    Ok((found, warnings))
+}
+
+/// Extract log entries from the log section
+/// Returns ( content before log section, content after log section, list of log entries, and detected list type)
+/// Section heading retrieved from yaml config
+///
+/// Entries are returned as they appear in the note, unless they have to be converted to
+/// `list_type` or a table header is requested, in which case they are rendered through
+/// [`Section::render_entries`](crate::document::Section::render_entries).
+pub fn extract_log_entries(
+    content: &str,
+    section_header: &str,
+    list_type: &ListType,
+    config: &Config,
+    include_header: bool,
+) -> (String, String, Vec<String>, ListType) {
+    let category = config.get_category_for_section_header(section_header);
+    let log = DailyLog::parse_with_headers(content, &[(section_header, category)], config);
+    let (before, section, after) = log.split_at_section(section_header);
+
+    let Some(section) = section else {
+        return (before, String::new(), Vec::new(), list_type.clone());
+    };
+
+    let before = before.trim_end().to_string() + "\n\n";
+    // The text after the section is kept as it was read, blank lines included
+    let after = match after.strip_suffix('\n') {
+        Some(stripped) if !content.ends_with('\n') => stripped.to_string(),
+        _ => after,
+    };
+    let found_type = section.list_type.clone().unwrap_or(list_type.clone());
+
+    let entries = if found_type == *list_type && !(*list_type == ListType::Table && include_header)
+    {
+        section.entries.iter().map(|e| e.raw.clone()).collect()
+    } else {
+        section.render_entries(list_type, config, include_header)
+    };
+
     (before, after, entries, found_type)
 }
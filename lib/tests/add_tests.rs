@@ -1,7 +1,11 @@
 use chrono::{Local, NaiveTime};
-use obsidian_logging::commands::add::{handle_plain_entry_with_time, handle_with_time};
+use obsidian_logging::commands::add::{
+    add_entry_for_date, add_entry_with_columns, handle_plain_entry_with_time, handle_with_time,
+};
+use obsidian_logging::commands::properties::set_properties;
 use obsidian_logging::config::{Config, ListType, TimeFormat};
 use obsidian_logging::utils::{extract_log_entries, get_log_path_for_date};
+use obsidian_logging::Error;
 use std::fs;
 use std::fs::{create_dir_all, read_to_string, write};
 use tempfile::TempDir;
@@ -20,6 +24,15 @@ fn setup_test_env() -> (TempDir, Config) {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
     (temp_dir, config)
 }
@@ -39,7 +52,8 @@ fn test_add_with_time_format() {
         &config,
         false,
         None,
-    );
+    )
+    .unwrap();
 
     let content = fs::read_to_string(&file_path).unwrap();
     assert!(content.contains("* 14:30:00 Test entry"));
@@ -53,7 +67,8 @@ fn test_add_with_time_format() {
         &config,
         false,
         None,
-    );
+    )
+    .unwrap();
 
     let content = fs::read_to_string(&file_path).unwrap();
     // The second entry gets incremented to 14:30:01 because the first entry is also 14:30:00
@@ -74,7 +89,7 @@ fn test_add_with_time_override() {
         "Test".to_string(),
         "entry".to_string(),
     ];
-    handle_with_time(args.into_iter(), &config, false, None);
+    handle_with_time(args.into_iter(), &config, false, None).unwrap();
 
     let content = fs::read_to_string(&file_path).unwrap();
     assert!(content.contains("* 14:30:00 Test entry"));
@@ -86,7 +101,7 @@ fn test_add_with_time_override() {
         "Another".to_string(),
         "test".to_string(),
     ];
-    handle_with_time(args.into_iter(), &config, false, None);
+    handle_with_time(args.into_iter(), &config, false, None).unwrap();
 
     let content = fs::read_to_string(&file_path).unwrap();
     // The second entry gets incremented to 14:30:01 because the first entry is also 14:30:00
@@ -109,7 +124,8 @@ fn test_add_with_table_format() {
         &config,
         false,
         None,
-    );
+    )
+    .unwrap();
 
     let content = fs::read_to_string(&file_path).unwrap();
     assert!(content.contains("| Tidspunkt | Hendelse |"));
@@ -125,7 +141,8 @@ fn test_add_with_table_format() {
         &config,
         false,
         None,
-    );
+    )
+    .unwrap();
 
     let content = fs::read_to_string(&file_path).unwrap();
     // The second entry gets incremented to 14:30:01 because the first entry is also 14:30:00
@@ -138,7 +155,7 @@ fn test_add_with_bullet_format() {
     config.list_type = ListType::Bullet;
 
     let now = Local::now();
-    let log_path = get_log_path_for_date(now.date_naive(), &config);
+    let log_path = get_log_path_for_date(now.date_naive(), &config).unwrap();
 
     // Create directory if it doesn't exist
     if let Some(parent) = log_path.parent() {
@@ -157,7 +174,8 @@ fn test_add_with_bullet_format() {
         &config,
         false,
         None,
-    );
+    )
+    .unwrap();
 
     // Read and verify content
     let content = read_to_string(&log_path).unwrap();
@@ -188,9 +206,188 @@ fn test_add_with_invalid_time_does_not_lose_first_word() {
         "a".to_string(),
         "test".to_string(),
     ];
-    handle_with_time(args.into_iter(), &config, false, None);
+    handle_with_time(args.into_iter(), &config, false, None).unwrap();
 
     let content = fs::read_to_string(&file_path).unwrap();
     // Should contain the full sentence including the invalid time
     assert!(content.contains("invalid_time This is a test"));
 }
+
+#[test]
+fn test_add_without_time_argument_returns_error() {
+    let (_temp_dir, config) = setup_test_env();
+
+    let result = handle_with_time(Vec::<String>::new().into_iter(), &config, false, None);
+    assert!(matches!(result, Err(Error::InvalidEntry(_))));
+}
+
+#[test]
+fn test_add_to_unwritable_vault_returns_io_error() {
+    let (temp_dir, mut config) = setup_test_env();
+
+    // A regular file where the log directory should be makes directory creation fail
+    let blocker = temp_dir.path().join("blocker");
+    write(&blocker, "not a directory").unwrap();
+    config.file_path_format = "blocker/{date}.md".to_string();
+
+    let result =
+        handle_plain_entry_with_time(vec!["Test entry".to_string()], None, &config, true, None);
+    assert!(matches!(result, Err(Error::Io { .. })));
+}
+
+#[test]
+fn test_add_to_missing_vault_creates_nothing() {
+    let (temp_dir, mut config) = setup_test_env();
+    let vault = temp_dir.path().join("mistyped");
+    config.vault = vault.to_str().unwrap().to_string();
+    config.file_path_format = "Journal/{year}/{date}.md".to_string();
+
+    let result =
+        handle_plain_entry_with_time(vec!["Test entry".to_string()], None, &config, true, None);
+    assert!(matches!(result, Err(Error::ConfigInvalid { .. })));
+    let result = set_properties(
+        Local::now().date_naive(),
+        &[("mood".to_string(), serde_yaml::Value::from(5))],
+        &config,
+        true,
+        None,
+    );
+    assert!(matches!(result, Err(Error::ConfigInvalid { .. })));
+    assert!(!vault.exists());
+}
+
+#[test]
+fn test_add_entry_for_past_date_creates_note_from_template() {
+    let (temp_dir, mut config) = setup_test_env();
+    let date = Local::now().date_naive() - chrono::Duration::days(3);
+    let file_path = temp_dir.path().join(format!("{}.md", date));
+
+    let template_path = temp_dir.path().join("template.md");
+    write(&template_path, "# {today}\n\n## Test\n\n").unwrap();
+    config.template_path = Some(template_path.to_str().unwrap().to_string());
+
+    let time = NaiveTime::from_hms_opt(21, 0, 0).unwrap();
+    add_entry_for_date(
+        date,
+        vec!["Forgot this".to_string()],
+        Some(time),
+        &config,
+        false,
+        None,
+    )
+    .unwrap();
+
+    let content = fs::read_to_string(&file_path).unwrap();
+    // Template variables refer to the date of the note, not the current date
+    assert!(content.starts_with(&format!("# {}\n", date)));
+    assert!(content.contains("* 21:00:00 Forgot this"));
+}
+
+#[test]
+fn test_add_entry_to_table_from_template() {
+    let (temp_dir, mut config) = setup_test_env();
+    let date = Local::now().date_naive();
+    let file_path = temp_dir.path().join(format!("{}.md", date));
+    config.list_type = ListType::Table;
+
+    let template_path = temp_dir.path().join("template.md");
+    write(
+        &template_path,
+        "# {today}\n\n## Test\n\n| Tidspunkt | Hendelse |\n|-----------|----------|\n\n## Notes\n",
+    )
+    .unwrap();
+    config.template_path = Some(template_path.to_str().unwrap().to_string());
+
+    let time = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+    add_entry_for_date(
+        date,
+        vec!["Standup".to_string()],
+        Some(time),
+        &config,
+        true,
+        None,
+    )
+    .unwrap();
+
+    // The entry goes into the table of the template rather than a new one
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert_eq!(
+        content,
+        format!(
+            "# {}\n\n## Test\n\n| Tidspunkt | Hendelse |\n| --------- | -------- |\n| 09:00:00 | Standup |\n\n## Notes\n",
+            date
+        )
+    );
+
+    let time = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+    add_entry_for_date(
+        date,
+        vec!["Coffee".to_string()],
+        Some(time),
+        &config,
+        true,
+        None,
+    )
+    .unwrap();
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert_eq!(content.matches("| Tidspunkt |").count(), 1);
+    assert!(content.contains("| 08:00:00 | Coffee |\n| 09:00:00 | Standup |\n\n## Notes\n"));
+}
+
+#[test]
+fn test_add_with_table_columns() {
+    let (temp_dir, mut config) = setup_test_env();
+    let date = Local::now().date_naive();
+    let file_path = temp_dir.path().join(format!("{}.md", date));
+    config.list_type = ListType::Table;
+    config.table_columns = vec![
+        "End".to_string(),
+        "Duration".to_string(),
+        "Tags".to_string(),
+    ];
+
+    let time = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+    let columns = vec![("end".to_string(), "10:30".to_string())];
+    add_entry_with_columns(
+        date,
+        vec!["Workshop #client".to_string()],
+        Some(time),
+        false,
+        &columns,
+        &config,
+        true,
+        None,
+    )
+    .unwrap();
+
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(content.contains("| Tidspunkt | Hendelse | End | Duration | Tags |"));
+    assert!(content.contains("| 09:00:00 | Workshop #client | 10:30 | 1:30 | #client |"));
+
+    // A plain entry leaves the columns empty
+    let time = NaiveTime::from_hms_opt(11, 0, 0).unwrap();
+    add_entry_for_date(
+        date,
+        vec!["Email".to_string()],
+        Some(time),
+        &config,
+        true,
+        None,
+    )
+    .unwrap();
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(content.contains("| 11:00:00 | Email |  |  |  |"));
+
+    let unknown = vec![("author".to_string(), "me".to_string())];
+    let result = add_entry_with_columns(
+        date,
+        vec!["Review".to_string()],
+        None,
+        false,
+        &unknown,
+        &config,
+        true,
+        None,
+    );
+    assert!(matches!(result, Err(Error::InvalidEntry(_))));
+}
@@ -0,0 +1,273 @@
+use chrono::{NaiveDate, NaiveTime};
+use obsidian_logging::commands::amend::{amend_entry, Amendment, EntrySelector};
+use obsidian_logging::config::{Config, ListType, TimeFormat};
+use obsidian_logging::Error;
+use std::fs::{read_to_string, write};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+fn setup_test_env() -> (TempDir, Config) {
+    let temp_dir = TempDir::new().unwrap();
+    let mut category_headers = std::collections::HashMap::new();
+    category_headers.insert("section_header_work".to_string(), "## Work".to_string());
+    let config = Config {
+        vault: temp_dir.path().to_str().unwrap().to_string(),
+        file_path_format: "{date}.md".to_string(),
+        section_header: "## Test".to_string(),
+        list_type: ListType::Bullet,
+        template_path: None,
+        locale: None,
+        time_format: TimeFormat::Hour24,
+        time_label: "Tidspunkt".to_string(),
+        event_label: "Hendelse".to_string(),
+        category_headers,
+        phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
+    };
+    (temp_dir, config)
+}
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+}
+
+fn write_note(temp_dir: &TempDir, content: &str) -> PathBuf {
+    let path = temp_dir.path().join("2024-01-15.md");
+    write(&path, content).unwrap();
+    path
+}
+
+fn time(h: u32, m: u32, s: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, s).unwrap()
+}
+
+#[test]
+fn test_parse_selector() {
+    assert_eq!(
+        "last".parse::<EntrySelector>().unwrap(),
+        EntrySelector::Last
+    );
+    assert_eq!(
+        "2".parse::<EntrySelector>().unwrap(),
+        EntrySelector::Index(2)
+    );
+    assert_eq!(
+        "14:30".parse::<EntrySelector>().unwrap(),
+        EntrySelector::Time(time(14, 30, 0))
+    );
+    assert_eq!(
+        "2:30 PM".parse::<EntrySelector>().unwrap(),
+        EntrySelector::Time(time(14, 30, 0))
+    );
+    assert!("0".parse::<EntrySelector>().is_err());
+    assert!("yesterday".parse::<EntrySelector>().is_err());
+}
+
+#[test]
+fn test_remove_entry_by_time() {
+    let (temp_dir, config) = setup_test_env();
+    let path = write_note(
+        &temp_dir,
+        "# Notes\n\n## Test\n\n* 09:00:00 First\n* 14:30:00 Second\n* 16:00:00 Third\n\n## Other\nKeep me\n",
+    );
+
+    amend_entry(
+        date(),
+        EntrySelector::Time(time(14, 30, 0)),
+        Amendment::Remove,
+        &config,
+        true,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        read_to_string(&path).unwrap(),
+        "# Notes\n\n## Test\n\n* 09:00:00 First\n* 16:00:00 Third\n\n## Other\nKeep me\n"
+    );
+}
+
+#[test]
+fn test_replace_text_of_last_entry() {
+    let (temp_dir, config) = setup_test_env();
+    let path = write_note(
+        &temp_dir,
+        "## Test\n\n- 09:00:00 First\n- 10:00:00 Secnod\n",
+    );
+
+    amend_entry(
+        date(),
+        EntrySelector::Last,
+        Amendment::Update {
+            text: Some("Second".to_string()),
+            time: None,
+        },
+        &config,
+        true,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        read_to_string(&path).unwrap(),
+        "## Test\n\n- 09:00:00 First\n- 10:00:00 Second\n"
+    );
+}
+
+#[test]
+fn test_change_time_resorts_and_realigns_table() {
+    let (temp_dir, config) = setup_test_env();
+    let path = write_note(
+        &temp_dir,
+        "## Work\n\n| Tidspunkt | Hendelse |\n|-----------|----------|\n| 09:00:00  | Early    |\n| 10:00:00  | Late     |\n",
+    );
+
+    amend_entry(
+        date(),
+        EntrySelector::Index(1),
+        Amendment::Update {
+            text: Some("Much later".to_string()),
+            time: Some(time(11, 0, 0)),
+        },
+        &config,
+        true,
+        Some("work"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        read_to_string(&path).unwrap(),
+        "## Work\n\n| Tidspunkt | Hendelse |\n| --------- | ---------- |\n| 10:00:00 | Late |\n| 11:00:00 | Much later |\n"
+    );
+}
+
+#[test]
+fn test_change_time_to_taken_timestamp_is_made_unique() {
+    let (temp_dir, config) = setup_test_env();
+    let path = write_note(
+        &temp_dir,
+        "## Test\n\n* 09:00:00 First\n* 10:00:00 Second\n",
+    );
+
+    amend_entry(
+        date(),
+        EntrySelector::Time(time(10, 0, 0)),
+        Amendment::Update {
+            text: None,
+            time: Some(time(9, 0, 0)),
+        },
+        &config,
+        true,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        read_to_string(&path).unwrap(),
+        "## Test\n\n* 09:00:00 First\n* 09:00:01 Second\n"
+    );
+}
+
+#[test]
+fn test_missing_entry_is_an_error() {
+    let (temp_dir, config) = setup_test_env();
+    let content = "## Test\n\n* 09:00:00 First\n";
+    let path = write_note(&temp_dir, content);
+
+    for selector in [EntrySelector::Index(2), EntrySelector::Time(time(8, 0, 0))] {
+        let result = amend_entry(date(), selector, Amendment::Remove, &config, true, None);
+        assert!(matches!(result, Err(Error::EntryNotFound(_))));
+    }
+    let result = amend_entry(
+        date(),
+        EntrySelector::Last,
+        Amendment::Remove,
+        &config,
+        true,
+        Some("work"),
+    );
+    assert!(matches!(result, Err(Error::EntryNotFound(_))));
+    assert_eq!(read_to_string(&path).unwrap(), content);
+
+    let result = amend_entry(
+        date() - chrono::Duration::days(1),
+        EntrySelector::Last,
+        Amendment::Remove,
+        &config,
+        true,
+        None,
+    );
+    assert!(matches!(result, Err(Error::EntryNotFound(_))));
+}
+
+#[test]
+fn test_complete_task() {
+    let (temp_dir, config) = setup_test_env();
+    let path = write_note(
+        &temp_dir,
+        "## Test\n\n- 08:00:00 Woke up\n- [ ] 09:00:00 Pay rent\n- [ ] 10:00:00 Call the bank\n",
+    );
+
+    let complete = |selector, completed_at| {
+        amend_entry(
+            date(),
+            selector,
+            Amendment::Complete { completed_at },
+            &config,
+            true,
+            None,
+        )
+    };
+    complete(EntrySelector::Time(time(9, 0, 0)), None).unwrap();
+    let completed_at = date().and_time(time(15, 2, 11));
+    complete(EntrySelector::Last, Some(completed_at)).unwrap();
+    // Already done, so the stamp is not added again
+    complete(EntrySelector::Last, Some(completed_at)).unwrap();
+
+    assert_eq!(
+        read_to_string(&path).unwrap(),
+        "## Test\n\n- 08:00:00 Woke up\n- [x] 09:00:00 Pay rent\n- [x] 10:00:00 Call the bank ✅ 2024-01-15 15:02:11\n"
+    );
+
+    let result = complete(EntrySelector::Index(1), None);
+    assert!(matches!(result, Err(Error::InvalidEntry(_))));
+}
+
+#[test]
+fn test_change_time_recomputes_duration() {
+    let (temp_dir, mut config) = setup_test_env();
+    config.table_columns = vec!["End".to_string(), "Duration".to_string()];
+    let path = write_note(
+        &temp_dir,
+        "## Test\n\n| Tidspunkt | Hendelse | End | Duration |\n| --- | --- | --- | --- |\n| 09:00:00 | Workshop | 10:30 | 1:30 |\n| 11:00:00 | Review | 12:00 | 2:00 |\n",
+    );
+
+    for index in [1, 2] {
+        amend_entry(
+            date(),
+            EntrySelector::Index(index),
+            Amendment::Update {
+                text: None,
+                time: Some(time(8 + index as u32, 30, 0)),
+            },
+            &config,
+            true,
+            None,
+        )
+        .unwrap();
+    }
+
+    // The duration filled in from the end follows the time, one given by hand is kept
+    assert_eq!(
+        read_to_string(&path).unwrap(),
+        "## Test\n\n| Tidspunkt | Hendelse | End | Duration |\n| --------- | -------- | ----- | -------- |\n| 09:30:00 | Workshop | 10:30 | 1:00 |\n| 10:30:00 | Review | 12:00 | 2:00 |\n"
+    );
+}
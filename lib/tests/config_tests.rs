@@ -1,5 +1,8 @@
 use lazy_static::lazy_static;
-use obsidian_logging::config::{Config, ListType, TimeFormat};
+use obsidian_logging::config::{
+    check_config_files, config_files, unknown_keys, unknown_profile_keys, Config, ListType,
+    TimeFormat,
+};
 use serial_test::serial;
 use std::env;
 use std::fs;
@@ -40,6 +43,7 @@ fn setup_test_env() -> PathBuf {
     config_dir
 }
 
+#[allow(clippy::manual_strip)]
 fn expand_tilde(path: &str) -> String {
     if path.starts_with("~/") {
         if let Some(home) = dirs::home_dir() {
@@ -114,12 +118,21 @@ fn test_load_config_existing() {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     let yaml = serde_yaml::to_string(&test_config).unwrap();
     fs::write(&config_path, yaml).unwrap();
 
-    let loaded_config = Config::initialize();
+    let loaded_config = Config::initialize().unwrap();
     assert_eq!(test_config.vault, loaded_config.vault);
     assert_eq!(test_config.file_path_format, loaded_config.file_path_format);
     assert_eq!(test_config.section_header, loaded_config.section_header);
@@ -168,6 +181,15 @@ fn test_config_serialization() {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     let serialized = serde_yaml::to_string(&config).unwrap();
@@ -224,6 +246,15 @@ fn test_config_with_time_format() {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     let config_12h = config.with_time_format(TimeFormat::Hour12);
@@ -247,6 +278,15 @@ fn test_config_with_list_type() {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     let config_bullet = config.with_list_type(ListType::Bullet);
@@ -285,6 +325,15 @@ fn test_environment_variable_overrides_config() {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
     let yaml = serde_yaml::to_string(&test_config).unwrap();
@@ -295,7 +344,7 @@ fn test_environment_variable_overrides_config() {
     assert_eq!(env::var("OBSIDIAN_VAULT_DIR").unwrap(), "/env/vault");
 
     // Load config - should use environment variable value
-    let loaded_config = Config::initialize();
+    let loaded_config = Config::initialize().unwrap();
     assert_eq!(loaded_config.vault, "/env/vault");
     assert_eq!(loaded_config.file_path_format, test_config.file_path_format);
     assert_eq!(loaded_config.section_header, test_config.section_header);
@@ -308,3 +357,374 @@ fn test_environment_variable_overrides_config() {
     // Clean up
     env::remove_var("OBSIDIAN_VAULT_DIR");
 }
+
+#[test]
+fn test_parse_error_reports_line_and_column() {
+    let path = PathBuf::from("obsidian-logging.yaml");
+    let error = Config::from_yaml("vault: /tmp\nlist_type: tabel\n", &path).unwrap_err();
+    match &error {
+        obsidian_logging::Error::ConfigParse { line, column, .. } => {
+            assert_eq!(*line, Some(2));
+            assert_eq!(*column, Some(12));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(error.to_string().starts_with(
+        "Invalid configuration in obsidian-logging.yaml:2:12: list_type: Invalid list type 'tabel'"
+    ));
+
+    let error = Config::from_yaml("vault: /tmp\ntime_format: 13\n", &path).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("Invalid configuration in obsidian-logging.yaml:2:14:"));
+}
+
+#[test]
+fn test_unknown_keys() {
+    let content = "vault: /tmp\nlist_typ: table\nsection_header_work: \"## Work\"\nphrases:\n  gym: Went to the gym\n# comment: here\n";
+    assert_eq!(unknown_keys(content), vec![(2, "list_typ".to_string())]);
+}
+
+#[test]
+#[serial]
+fn test_check_config_files() {
+    env::remove_var("OBSIDIAN_VAULT_DIR");
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("obsidian-logging.yaml");
+    let files = vec![path.clone()];
+
+    fs::write(
+        &path,
+        format!(
+            "vault: {}\ntemplate_path: {}\n",
+            temp_dir.path().display(),
+            path.display()
+        ),
+    )
+    .unwrap();
+    assert!(check_config_files(&files, None).is_ok());
+
+    fs::write(
+        &path,
+        "vault: /does/not/exist\ntemplate_path: /no/template.md\ncolour: blue\n",
+    )
+    .unwrap();
+    match check_config_files(&files, None) {
+        Err(obsidian_logging::Error::ConfigInvalid { problems, .. }) => assert_eq!(
+            problems,
+            vec![
+                format!("{}:3: unknown key 'colour'", path.display()),
+                "vault directory '/does/not/exist' does not exist".to_string(),
+                "template_path '/no/template.md' does not exist".to_string(),
+            ]
+        ),
+        other => panic!("unexpected result: {:?}", other.map(|c| c.vault)),
+    }
+
+    fs::write(&path, "vault: [unclosed\n").unwrap();
+    assert!(matches!(
+        check_config_files(&files, None),
+        Err(obsidian_logging::Error::ConfigParse { .. })
+    ));
+}
+
+#[test]
+#[serial]
+fn test_ensure_vault() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config {
+        vault: String::new(),
+        ..Config::default()
+    };
+    assert!(config.ensure_vault().is_err());
+    config.vault = temp_dir
+        .path()
+        .join("missing")
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(config.ensure_vault().is_err());
+    config.vault = temp_dir.path().to_str().unwrap().to_string();
+    assert!(config.ensure_vault().is_ok());
+}
+
+#[test]
+fn test_empty_vault_is_not_resolved() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.yaml");
+    fs::write(&path, "vault: \"\"\nprofiles:\n  work:\n    vault: \" \"\n").unwrap();
+
+    let config = Config::load_layered(std::slice::from_ref(&path)).unwrap();
+    assert_eq!(config.vault, "");
+    assert!(config.ensure_vault().is_err());
+
+    let config = Config::load_profile(&[path], Some("work")).unwrap();
+    assert_eq!(config.vault, " ");
+    assert!(config.ensure_vault().is_err());
+}
+
+#[test]
+#[serial]
+fn test_ensure_vault_expands_tilde() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir(temp_dir.path().join("notes")).unwrap();
+    let home = env::var_os("HOME");
+    env::set_var("HOME", temp_dir.path());
+
+    let config = Config {
+        vault: "~/notes".to_string(),
+        ..Config::default()
+    };
+    let result = config.ensure_vault();
+    let vault_path = config.vault_path();
+    match home {
+        Some(home) => env::set_var("HOME", home),
+        None => env::remove_var("HOME"),
+    }
+    assert!(result.is_ok());
+    assert_eq!(vault_path, temp_dir.path().join("notes"));
+}
+
+#[test]
+#[serial]
+fn test_layered_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let user = temp_dir.path().join("user.yaml");
+    let project_dir = temp_dir.path().join("project");
+    fs::create_dir_all(&project_dir).unwrap();
+    let project = project_dir.join(".obsidian-logging.yaml");
+
+    fs::write(
+        &user,
+        "vault: /home/me/vault\nsection_header: \"## Log\"\nlist_type: table\nphrases:\n  gym: Went to the gym\n  lunch: Lunch\n",
+    )
+    .unwrap();
+    fs::write(
+        &project,
+        "vault: notes\nsection_header: \"## Project\"\nphrases:\n  lunch: Team lunch\n",
+    )
+    .unwrap();
+
+    let config = Config::load_layered(&[user, project]).unwrap();
+    assert_eq!(
+        config.vault,
+        project_dir.join("notes").to_str().unwrap().to_string()
+    );
+    assert_eq!(config.section_header, "## Project");
+    assert_eq!(config.list_type, ListType::Table);
+    assert_eq!(config.get_phrase("gym").unwrap(), "Went to the gym");
+    assert_eq!(config.get_phrase("lunch").unwrap(), "Team lunch");
+}
+
+#[test]
+#[serial]
+fn test_layered_config_reports_the_failing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let user = temp_dir.path().join("user.yaml");
+    let project = temp_dir.path().join(".obsidian-logging.yaml");
+    fs::write(&user, "vault: /tmp\n").unwrap();
+    fs::write(&project, "time_format: 25\n").unwrap();
+
+    match Config::load_layered(&[user, project.clone()]) {
+        Err(obsidian_logging::Error::ConfigParse { path, line, .. }) => {
+            assert_eq!(path, project);
+            assert_eq!(line, Some(1));
+        }
+        other => panic!("unexpected result: {:?}", other.map(|c| c.vault)),
+    }
+}
+
+#[test]
+#[serial]
+fn test_config_files_discovery() {
+    let temp_dir = TempDir::new().unwrap();
+    let explicit = temp_dir.path().join("explicit.yaml");
+
+    assert_eq!(config_files(Some(&explicit)), vec![explicit.clone()]);
+
+    env::set_var("OBSIDIAN_LOGGING_CONFIG", &explicit);
+    assert_eq!(config_files(None), vec![explicit.clone()]);
+    env::remove_var("OBSIDIAN_LOGGING_CONFIG");
+
+    // Missing explicit files are an error, not silently the defaults
+    assert!(Config::initialize_from(Some(&explicit), None).is_err());
+}
+
+#[test]
+#[serial]
+fn test_empty_config_file_gives_defaults() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("empty.yaml");
+    fs::write(&path, "# nothing configured yet\n").unwrap();
+
+    let config = Config::load_layered(&[path]).unwrap();
+    assert_eq!(config.section_header, "## 🕗");
+}
+
+const PROFILES_YAML: &str = "vault: /home/me/private
+section_header: \"## Log\"
+list_type: table
+default_profile: private
+phrases:
+  gym: Went to the gym
+profiles:
+  private:
+  work:
+    vault: work-notes
+    section_header_meeting: \"## Meetings\"
+    list_type: bullet
+    phrases:
+      standup: Daily standup
+";
+
+#[test]
+#[serial]
+fn test_profiles_override_top_level_settings() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.yaml");
+    fs::write(&path, PROFILES_YAML).unwrap();
+
+    let config = Config::load_profile(std::slice::from_ref(&path), Some("work")).unwrap();
+    assert_eq!(config.profile.as_deref(), Some("work"));
+    assert_eq!(
+        config.vault,
+        temp_dir.path().join("work-notes").to_str().unwrap()
+    );
+    assert_eq!(config.section_header, "## Log");
+    assert_eq!(config.list_type, ListType::Bullet);
+    assert_eq!(
+        config.get_section_header_for_category(Some("meeting")),
+        "## Meetings"
+    );
+    assert_eq!(config.get_phrase("gym").unwrap(), "Went to the gym");
+    assert_eq!(config.get_phrase("standup").unwrap(), "Daily standup");
+
+    // Without a name the default profile is used
+    let config = Config::load_layered(&[path]).unwrap();
+    assert_eq!(config.profile.as_deref(), Some("private"));
+    assert_eq!(config.vault, "/home/me/private");
+    assert_eq!(config.list_type, ListType::Table);
+}
+
+#[test]
+#[serial]
+fn test_undefined_profile_is_an_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.yaml");
+    fs::write(&path, PROFILES_YAML).unwrap();
+
+    match Config::load_profile(&[path], Some("home")) {
+        Err(obsidian_logging::Error::ConfigInvalid { problems, .. }) => {
+            assert_eq!(
+                problems,
+                vec!["profile 'home' is not defined (available: private, work)"]
+            );
+        }
+        other => panic!("unexpected result: {:?}", other.map(|c| c.vault)),
+    }
+    assert!(Config::load_profile(&[], Some("work")).is_err());
+}
+
+#[test]
+#[serial]
+fn test_profile_selected_by_environment() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.yaml");
+    fs::write(&path, PROFILES_YAML).unwrap();
+    env::remove_var("OBSIDIAN_VAULT_DIR");
+
+    env::set_var("OBSIDIAN_LOGGING_PROFILE", "work");
+    let config = Config::initialize_from(Some(&path), None).unwrap();
+    assert_eq!(config.profile.as_deref(), Some("work"));
+
+    // The flag wins over the environment
+    let config = Config::initialize_from(Some(&path), Some("private")).unwrap();
+    assert_eq!(config.profile.as_deref(), Some("private"));
+    env::remove_var("OBSIDIAN_LOGGING_PROFILE");
+}
+
+#[test]
+#[serial]
+fn test_profile_vault_wins_over_environment() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.yaml");
+    fs::write(&path, PROFILES_YAML).unwrap();
+    env::remove_var("OBSIDIAN_LOGGING_PROFILE");
+    env::set_var("OBSIDIAN_VAULT_DIR", "/tmp/env-vault");
+
+    let config = Config::initialize_from(Some(&path), Some("work")).unwrap();
+    assert_eq!(
+        config.vault,
+        temp_dir.path().join("work-notes").to_str().unwrap()
+    );
+
+    // A profile without a vault still takes it from the environment
+    let config = Config::initialize_from(Some(&path), Some("private")).unwrap();
+    assert_eq!(config.vault, "/tmp/env-vault");
+    env::remove_var("OBSIDIAN_VAULT_DIR");
+}
+
+#[test]
+fn test_unknown_profile_keys() {
+    let content = "profiles:\n  work:\n    vault: /tmp\n    list_typ: table\n    profiles: {}\n";
+    assert_eq!(
+        unknown_profile_keys(content),
+        vec![
+            ("work".to_string(), "list_typ".to_string()),
+            ("work".to_string(), "profiles".to_string()),
+        ]
+    );
+    assert!(unknown_keys(content).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_profile_values_are_checked_with_location() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.yaml");
+    fs::write(&path, "profiles:\n  work:\n    list_type: tabel\n").unwrap();
+
+    match Config::load_layered(&[path]) {
+        Err(obsidian_logging::Error::ConfigParse { line, .. }) => assert_eq!(line, Some(3)),
+        other => panic!("unexpected result: {:?}", other.map(|c| c.vault)),
+    }
+}
+
+#[test]
+fn test_custom_list_type_with_entry_format() {
+    let config: Config =
+        serde_yaml::from_str("list_type: custom\nentry_format: \"- **{time}** — {text}\"\n")
+            .unwrap();
+    assert_eq!(config.list_type, ListType::Custom);
+    assert_eq!(
+        config.entry_format.as_ref().map(|f| f.as_str()),
+        Some("- **{time}** — {text}")
+    );
+
+    let error = serde_yaml::from_str::<Config>("entry_format: \"- {text}\"\n").unwrap_err();
+    assert!(error.to_string().contains("{time}"), "{}", error);
+
+    let missing: Config = serde_yaml::from_str("list_type: custom\n").unwrap();
+    assert!(missing
+        .problems()
+        .iter()
+        .any(|p| p.contains("no entry_format")));
+}
+
+#[test]
+fn test_section_patterns_and_end_marker() {
+    let config: Config = serde_yaml::from_str(
+        "section_patterns:\n  default: \"^## 🕗\"\n  work: \"^## (💼 )?Work$\"\nsection_end: \"---\"\n",
+    )
+    .unwrap();
+    assert_eq!(config.section_patterns["work"], "^## (💼 )?Work$");
+    assert_eq!(config.section_end.as_deref(), Some("---"));
+
+    let error =
+        serde_yaml::from_str::<Config>("section_patterns:\n  default: \"## (\"\n").unwrap_err();
+    assert!(
+        error.to_string().contains("invalid pattern for 'default'"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,561 @@
+use chrono::{NaiveDate, NaiveTime};
+use obsidian_logging::config::{Config, ListType, TimeFormat};
+use obsidian_logging::document::{DailyLog, LogEntry, TaskStatus};
+
+fn create_test_config() -> Config {
+    let mut category_headers = std::collections::HashMap::new();
+    category_headers.insert("section_header_work".to_string(), "## Work".to_string());
+    Config {
+        vault: "/test/vault".to_string(),
+        file_path_format: "{date}.md".to_string(),
+        section_header: "## Test".to_string(),
+        list_type: ListType::Bullet,
+        template_path: None,
+        locale: None,
+        time_format: TimeFormat::Hour24,
+        time_label: "Tidspunkt".to_string(),
+        event_label: "Hendelse".to_string(),
+        category_headers,
+        phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
+    }
+}
+
+const NOTE: &str = r#"---
+tags: [daily]
+---
+# 2024-03-15
+
+Some notes
+
+## Test
+
+* 09:00 First entry
+* 02:15 PM Second entry
+
+## Work
+
+| Tidspunkt | Hendelse |
+|-----------|----------|
+| 10:30 | Standup |
+
+## Other
+
+Free text
+"#;
+
+#[test]
+fn test_parse_entries_and_sections() {
+    let config = create_test_config();
+    let log = DailyLog::parse(NOTE, &config);
+
+    let front_matter = log.front_matter.as_ref().unwrap();
+    assert_eq!(front_matter.raw(), "tags: [daily]\n");
+    assert_eq!(
+        front_matter.get("tags"),
+        Some(&serde_yaml::from_str("[daily]").unwrap())
+    );
+
+    let sections: Vec<_> = log.sections().collect();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].header, "## Test");
+    assert_eq!(sections[0].list_type, Some(ListType::Bullet));
+    assert_eq!(sections[1].category.as_deref(), Some("work"));
+    assert_eq!(sections[1].list_type, Some(ListType::Table));
+
+    let entries: Vec<&LogEntry> = log.entries().collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].time, NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+    assert_eq!(entries[0].text, "First entry");
+    assert_eq!(entries[0].category, None);
+    assert_eq!(entries[0].raw, "* 09:00 First entry");
+    assert_eq!(entries[1].time, NaiveTime::from_hms_opt(14, 15, 0).unwrap());
+    assert_eq!(entries[2].text, "Standup");
+    assert_eq!(entries[2].category.as_deref(), Some("work"));
+}
+
+#[test]
+fn test_render_unmodified_is_unchanged() {
+    let config = create_test_config();
+    let log = DailyLog::parse(NOTE, &config);
+    assert_eq!(log.render(&config), NOTE);
+}
+
+#[test]
+fn test_insert_rewrites_only_modified_section() {
+    let config = create_test_config();
+    let mut log = DailyLog::parse(NOTE, &config);
+
+    let section = log.section_mut("## Test").unwrap();
+    let time = section.insert(NaiveTime::from_hms_opt(9, 0, 0).unwrap(), "Coffee");
+    assert_eq!(time, NaiveTime::from_hms_opt(9, 0, 1).unwrap());
+
+    let rendered = log.render(&config);
+    assert!(rendered.contains(
+        "## Test\n\n* 09:00:00 First entry\n* 09:00:01 Coffee\n* 14:15:00 Second entry\n\n## Work"
+    ));
+    // Untouched table section keeps its original formatting
+    assert!(rendered.contains("| 10:30 | Standup |"));
+    assert!(rendered.starts_with("---\ntags: [daily]\n---\n# 2024-03-15"));
+}
+
+#[test]
+fn test_section_or_insert_appends_missing_section() {
+    let config = create_test_config();
+    let mut log = DailyLog::parse("# 2024-03-15\n", &config);
+
+    log.section_or_insert("## Work", Some("work"))
+        .insert(NaiveTime::from_hms_opt(8, 0, 0).unwrap(), "Planning");
+
+    assert_eq!(
+        log.render(&config),
+        "# 2024-03-15\n\n## Work\n\n* 08:00:00 Planning\n"
+    );
+    assert_eq!(
+        log.entries().next().unwrap().category.as_deref(),
+        Some("work")
+    );
+}
+
+#[test]
+fn test_render_entries_as_table() {
+    let config = create_test_config();
+    let log = DailyLog::parse(NOTE, &config);
+    let lines = log
+        .section("## Test")
+        .unwrap()
+        .render_entries(&ListType::Table, &config, true);
+
+    assert_eq!(
+        lines,
+        vec![
+            "| Tidspunkt | Hendelse     |",
+            "|-----------|--------------|",
+            "| 09:00:00  | First entry  |",
+            "| 14:15:00  | Second entry |",
+        ]
+    );
+}
+
+#[test]
+fn test_multi_line_bullet_entries_round_trip() {
+    let config = create_test_config();
+    let content = "## Test\n\n* 10:00:00 Build failed\n  error[E0308]: mismatched types\n      --> src/main.rs:4:5\n\n  see above\n* 09:00:00 Standup\n";
+    let mut log = DailyLog::parse(content, &config);
+
+    let entries: Vec<&LogEntry> = log.entries().collect();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(
+        entries[0].text,
+        "Build failed\nerror[E0308]: mismatched types\n    --> src/main.rs:4:5\n\nsee above"
+    );
+    assert_eq!(entries[1].text, "Standup");
+
+    // Re-sorting moves the continuation lines along with their entry
+    log.section_mut("## Test")
+        .unwrap()
+        .insert(NaiveTime::from_hms_opt(11, 0, 0).unwrap(), "Fixed");
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\n* 09:00:00 Standup\n* 10:00:00 Build failed\n  error[E0308]: mismatched types\n      --> src/main.rs:4:5\n\n  see above\n* 11:00:00 Fixed\n"
+    );
+}
+
+#[test]
+fn test_multi_line_table_entries_use_br() {
+    let mut config = create_test_config();
+    config.list_type = ListType::Table;
+    let mut log = DailyLog::parse("## Work\n", &config);
+    log.section_mut("## Work")
+        .unwrap()
+        .insert(NaiveTime::from_hms_opt(9, 0, 0).unwrap(), "First\nSecond");
+
+    let rendered = log.render(&config);
+    assert!(rendered.contains("| 09:00:00 | First<br>Second |"));
+
+    let reparsed = DailyLog::parse(&rendered, &config);
+    assert_eq!(reparsed.entries().next().unwrap().text, "First\nSecond");
+}
+
+#[test]
+fn test_unindented_line_ends_multi_line_entry() {
+    let config = create_test_config();
+    let log = DailyLog::parse(
+        "## Test\n\n* 09:00:00 Entry\n  more\nNot part of it\n  nor this\n",
+        &config,
+    );
+
+    let entries: Vec<&LogEntry> = log.entries().collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].text, "Entry\nmore");
+}
+
+#[test]
+fn test_tasks_survive_resorting_and_conversion() {
+    let mut config = create_test_config();
+    let mut log = DailyLog::parse(
+        "## Test\n\n- [ ] 10:00:00 Call the bank\n- [x] 09:00:00 Pay rent\n- 08:00:00 Woke up\n",
+        &config,
+    );
+    let tasks: Vec<Option<TaskStatus>> = log.entries().map(|e| e.task).collect();
+    assert_eq!(
+        tasks,
+        vec![Some(TaskStatus::Open), Some(TaskStatus::Done), None]
+    );
+    assert_eq!(log.entries().next().unwrap().text, "Call the bank");
+
+    log.section_mut("## Test")
+        .unwrap()
+        .insert_task(NaiveTime::from_hms_opt(9, 30, 0).unwrap(), "Book flight");
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\n- 08:00:00 Woke up\n- [x] 09:00:00 Pay rent\n- [ ] 09:30:00 Book flight\n- [ ] 10:00:00 Call the bank\n"
+    );
+
+    let section = log.section("## Test").unwrap();
+    let table = section.render_entries(&ListType::Table, &config, false);
+    assert_eq!(table[1], "| 09:00:00  | [x] Pay rent      |");
+    config.list_type = ListType::Table;
+    let reparsed = DailyLog::parse(&format!("## Test\n\n{}\n", table.join("\n")), &config);
+    let summary = |entries: &[LogEntry]| -> Vec<(NaiveTime, String, Option<TaskStatus>)> {
+        entries
+            .iter()
+            .map(|e| (e.time, e.text.clone(), e.task))
+            .collect()
+    };
+    assert_eq!(
+        summary(&reparsed.section("## Test").unwrap().entries),
+        summary(&section.entries)
+    );
+}
+
+#[test]
+fn test_table_keeps_columns_it_does_not_know() {
+    let mut config = create_test_config();
+    config.list_type = ListType::Table;
+    config.table_columns = vec!["Category".to_string()];
+    let content = "## Test\n\n\
+        | Time | Event | Author | Billable |\n\
+        |------|-------|--------|----------|\n\
+        | 10:00:00 | Review | Kari | yes |\n\
+        | 09:00:00 | Standup | Ola | no | extra |\n";
+    let mut log = DailyLog::parse(content, &config);
+    let section = log.section_mut("## Test").unwrap();
+    assert_eq!(section.entries[0].column("author"), Some("Kari"));
+    assert_eq!(
+        section.column_labels(&config),
+        vec!["Author", "Billable", "", "Category"]
+    );
+    section.insert(NaiveTime::from_hms_opt(11, 0, 0).unwrap(), "Lunch");
+
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\n\
+         | Tidspunkt | Hendelse | Author | Billable |  | Category |\n\
+         | --------- | -------- | ------ | -------- | ----- | -------- |\n\
+         | 09:00:00 | Standup | Ola | no | extra |  |\n\
+         | 10:00:00 | Review | Kari | yes |  |  |\n\
+         | 11:00:00 | Lunch |  |  |  |  |\n"
+    );
+}
+
+#[test]
+fn test_rewriting_a_section_keeps_content_it_does_not_own() {
+    let config = create_test_config();
+    let content = "## Test\n\n\
+        Morning:\n\
+        * 08:00:00 Coffee\n  \
+          - with milk\n\
+        * 09:00:00 Standup\n\
+        \n\
+        <!-- afternoon -->\n\
+        * 13:00:00 Review\n\
+        ![[whiteboard.png]]\n\
+        \n\
+        - a plain bullet\n\
+        \n\
+        ## Notes\n";
+    let mut log = DailyLog::parse(content, &config);
+    let section = log.section_mut("## Test").unwrap();
+    section.insert(NaiveTime::from_hms_opt(14, 0, 0).unwrap(), "Deploy");
+    section.insert(NaiveTime::from_hms_opt(8, 30, 0).unwrap(), "Email");
+
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\n\
+         Morning:\n\
+         * 08:00:00 Coffee\n  \
+           - with milk\n\
+         * 08:30:00 Email\n\
+         \n\
+         <!-- afternoon -->\n\
+         * 09:00:00 Standup\n\
+         * 13:00:00 Review\n\
+         * 14:00:00 Deploy\n\
+         ![[whiteboard.png]]\n\
+         \n\
+         - a plain bullet\n\
+         \n\
+         ## Notes\n"
+    );
+}
+
+#[test]
+fn test_rewriting_a_table_keeps_the_text_around_it() {
+    let mut config = create_test_config();
+    config.list_type = ListType::Table;
+    let content = "## Test\n\
+        Billable hours only.\n\
+        \n\
+        | Time | Event |\n\
+        |:-----|:------|\n\
+        | 10:00:00 | Review |\n\
+        | | (lunch) |\n\
+        \n\
+        > [!note] Invoice on Friday\n";
+    let mut log = DailyLog::parse(content, &config);
+    log.section_mut("## Test")
+        .unwrap()
+        .insert(NaiveTime::from_hms_opt(9, 0, 0).unwrap(), "Standup");
+
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\
+         Billable hours only.\n\
+         \n\
+         | Tidspunkt | Hendelse |\n\
+         | --------- | -------- |\n\
+         | 09:00:00 | Standup |\n\
+         | 10:00:00 | Review |\n\
+         | | (lunch) |\n\
+         \n\
+         > [!note] Invoice on Friday\n"
+    );
+
+    // A section that is only read is written back unchanged
+    let unchanged = DailyLog::parse(content, &config);
+    assert_eq!(unchanged.render(&config), content);
+}
+
+#[test]
+fn test_section_ends_at_a_heading_of_the_same_level() {
+    let config = create_test_config();
+    let content = "## Test\n\
+        * 09:00:00 Standup\n\
+        ### Details\n\
+        * 10:00:00 Review\n\
+        ## Test Archive\n\
+        * 08:00:00 Old entry\n\
+        # Appendix\n";
+    let mut log = DailyLog::parse(content, &config);
+    let section = log.section_mut("## Test").unwrap();
+    let texts: Vec<&str> = section.entries.iter().map(|e| e.text.as_str()).collect();
+    assert_eq!(texts, vec!["Standup", "Review"]);
+    section.insert(NaiveTime::from_hms_opt(9, 30, 0).unwrap(), "Coffee");
+
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\
+         * 09:00:00 Standup\n\
+         ### Details\n\
+         * 09:30:00 Coffee\n\
+         * 10:00:00 Review\n\
+         ## Test Archive\n\
+         * 08:00:00 Old entry\n\
+         # Appendix\n"
+    );
+}
+
+#[test]
+fn test_section_end_marker_and_header_pattern() {
+    let mut config = create_test_config();
+    config.section_end = Some("<!-- end log -->".to_string());
+    config
+        .section_patterns
+        .insert("default".to_string(), r"^## (Test|Log)\b".to_string());
+    let content = "## Log for today\n\
+        * 09:00:00 Standup\n\
+        <!-- end log -->\n\
+        * 10:00:00 Not an entry\n";
+    let log = DailyLog::parse(content, &config);
+
+    let section = log.section("## Test").unwrap();
+    assert_eq!(section.header, "## Log for today");
+    assert_eq!(section.entries.len(), 1);
+    assert_eq!(log.render(&config), content);
+}
+
+#[test]
+fn test_table_columns_align_by_display_width() {
+    let mut config = create_test_config();
+    config.list_type = ListType::Table;
+    let mut log = DailyLog::parse("## Test\n", &config);
+    let section = log.section_mut("## Test").unwrap();
+    for (hour, text) in [
+        (9, "Kaffe på kjøkkenet"),
+        (10, "Deploy 🚀"),
+        (11, "会议"),
+        (12, "Lunch"),
+    ] {
+        section.insert(NaiveTime::from_hms_opt(hour, 0, 0).unwrap(), text);
+    }
+
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\n\
+         | Tidspunkt | Hendelse |\n\
+         | --------- | ------------------ |\n\
+         | 09:00:00 | Kaffe på kjøkkenet |\n\
+         | 10:00:00 | Deploy 🚀 |\n\
+         | 11:00:00 | 会议 |\n\
+         | 12:00:00 | Lunch |\n"
+    );
+
+    // Listed tables are padded to the widest cell of each column
+    let section = log.section("## Test").unwrap();
+    assert_eq!(
+        section.render_entries(&ListType::Table, &config, true),
+        vec![
+            "| Tidspunkt | Hendelse           |",
+            "|-----------|--------------------|",
+            "| 09:00:00  | Kaffe på kjøkkenet |",
+            "| 10:00:00  | Deploy 🚀          |",
+            "| 11:00:00  | 会议               |",
+            "| 12:00:00  | Lunch              |",
+        ]
+    );
+}
+
+#[test]
+fn test_table_keeps_its_header_without_configured_labels() {
+    let config = Config {
+        list_type: ListType::Table,
+        time_label: "Time".to_string(),
+        event_label: "Event".to_string(),
+        ..create_test_config()
+    };
+    let note =
+        "## Test\n\n| Tidspunkt | Hendelse |\n| --------- | -------- |\n| 09:00:00 | Kaffe |\n";
+    let mut log = DailyLog::parse(note, &config);
+    log.section_mut("## Test")
+        .unwrap()
+        .insert(NaiveTime::from_hms_opt(10, 0, 0).unwrap(), "Standup");
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\n\
+         | Tidspunkt | Hendelse |\n\
+         | --------- | -------- |\n\
+         | 09:00:00 | Kaffe |\n\
+         | 10:00:00 | Standup |\n"
+    );
+
+    // New tables get the default labels
+    let mut log = DailyLog::parse("## Test\n", &config);
+    log.section_mut("## Test")
+        .unwrap()
+        .insert(NaiveTime::from_hms_opt(10, 0, 0).unwrap(), "Standup");
+    assert!(log.render(&config).contains("| Time | Event |\n"));
+}
+
+#[test]
+fn test_pipes_in_table_cells_round_trip() {
+    let mut config = create_test_config();
+    config.list_type = ListType::Table;
+    config.table_columns = vec!["Notes".to_string()];
+    let texts = [
+        "Compared a | b",
+        "Read [[Project plan|the plan]]",
+        r"Escaped \| pipe",
+    ];
+    let mut log = DailyLog::parse("## Test\n", &config);
+    let section = log.section_mut("## Test").unwrap();
+    for (hour, text) in texts.iter().enumerate() {
+        let time = NaiveTime::from_hms_opt(9 + hour as u32, 0, 0).unwrap();
+        section.insert_entry(
+            None,
+            time,
+            text,
+            None,
+            vec![("Notes".to_string(), "x|y".to_string())],
+        );
+    }
+
+    let rendered = log.render(&config);
+    assert!(rendered.contains(r"| Compared a \| b | x\|y |"));
+    assert!(rendered.contains(r"| Read [[Project plan\|the plan]] | x\|y |"));
+    assert!(rendered.contains(r"| Escaped \\| pipe | x\|y |"));
+
+    let reparsed = DailyLog::parse(&rendered, &config);
+    let entries = &reparsed.section("## Test").unwrap().entries;
+    let parsed: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+    assert_eq!(parsed, texts);
+    assert_eq!(entries[0].column("Notes"), Some("x|y"));
+    assert_eq!(reparsed.render(&config), rendered);
+
+    // Wikilinks written by hand without escaping their pipe stay in one cell
+    let entry = LogEntry::parse("| 14:00:00 | See [[Note|alias]] |", None).unwrap();
+    assert_eq!(entry.text, "See [[Note|alias]]");
+}
+
+#[test]
+fn test_unclosed_wikilink_does_not_merge_table_cells() {
+    let mut config = create_test_config();
+    config.table_columns = vec!["Notes".to_string(), "Tags".to_string()];
+    let note = "## Test\n\n| Time | Event | Notes | Tags |\n|---|---|---|---|\n| 09:00:00 | Open [[ bracket | a | b ]] |\n";
+    let log = DailyLog::parse(note, &config);
+    let entry = &log.section("## Test").unwrap().entries[0];
+    assert_eq!(entry.text, "Open [[ bracket");
+    assert_eq!(entry.column("Notes"), Some("a"));
+    assert_eq!(entry.column("Tags"), Some("b ]]"));
+}
+
+#[test]
+fn test_parse_dated_entries() {
+    let date = NaiveDate::from_ymd_opt(2026, 10, 15);
+    for line in [
+        "* 2026-10-15 21:05:00 Retro",
+        "- [ ] 2026-10-15 09:05 PM Retro",
+        "| 2026-10-15 21:05:00 | Retro |",
+    ] {
+        let entry = LogEntry::parse(line, None).unwrap();
+        assert_eq!(entry.date, date, "{}", line);
+        assert_eq!(entry.time, NaiveTime::from_hms_opt(21, 5, 0).unwrap());
+        assert_eq!(entry.text, "Retro");
+    }
+
+    let entry = LogEntry::parse("* 21:05:00 2026-10-15 was a Thursday", None).unwrap();
+    assert_eq!(entry.date, None);
+    assert_eq!(entry.text, "2026-10-15 was a Thursday");
+}
+
+#[test]
+fn test_tasks_are_written_with_dashes() {
+    let config = create_test_config();
+    let mut log = DailyLog::parse("## Test\n\n* 09:00:00 Standup\n", &config);
+    log.section_mut("## Test")
+        .unwrap()
+        .insert_task(NaiveTime::from_hms_opt(10, 0, 0).unwrap(), "Call the bank");
+    let rendered = log.render(&config);
+    assert_eq!(
+        rendered,
+        "## Test\n\n* 09:00:00 Standup\n- [ ] 10:00:00 Call the bank\n"
+    );
+
+    // A task first in the section does not change the bullet of plain entries
+    let mut log = DailyLog::parse("## Test\n\n- [ ] 08:00:00 Pay rent\n", &config);
+    log.section_mut("## Test")
+        .unwrap()
+        .insert(NaiveTime::from_hms_opt(9, 0, 0).unwrap(), "Standup");
+    assert_eq!(
+        log.render(&config),
+        "## Test\n\n- [ ] 08:00:00 Pay rent\n* 09:00:00 Standup\n"
+    );
+}
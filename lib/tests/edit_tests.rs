@@ -20,6 +20,15 @@ fn setup_test_env() -> (TempDir, Config) {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
     (temp_dir, config)
 }
@@ -28,7 +37,7 @@ fn setup_test_env() -> (TempDir, Config) {
 fn test_edit_today() {
     let (_temp_dir, config) = setup_test_env();
     let today = Local::now().date_naive();
-    let file_path = get_log_path_for_date(today, &config);
+    let file_path = get_log_path_for_date(today, &config).unwrap();
 
     // Create parent directory
     if let Some(parent) = file_path.parent() {
@@ -47,7 +56,7 @@ fn test_edit_today() {
     env::set_var("EDITOR", "echo");
 
     // Test editing today's file
-    edit_log_for_day(0, &config, false);
+    edit_log_for_day(0, &config, None).unwrap();
 
     // Verify the file still exists and has the same content
     assert!(file_path.exists());
@@ -59,7 +68,7 @@ fn test_edit_today() {
 fn test_edit_relative_day() {
     let (_temp_dir, config) = setup_test_env();
     let yesterday = Local::now().date_naive() - Duration::days(1);
-    let file_path = get_log_path_for_date(yesterday, &config);
+    let file_path = get_log_path_for_date(yesterday, &config).unwrap();
 
     // Create parent directory
     if let Some(parent) = file_path.parent() {
@@ -78,7 +87,7 @@ fn test_edit_relative_day() {
     env::set_var("EDITOR", "echo");
 
     // Test editing yesterday's file
-    edit_log_for_day(1, &config, false);
+    edit_log_for_day(1, &config, None).unwrap();
 
     // Verify the file still exists and has the same content
     assert!(file_path.exists());
@@ -90,7 +99,7 @@ fn test_edit_relative_day() {
 fn test_edit_nonexistent_file() {
     let (_temp_dir, config) = setup_test_env();
     let tomorrow = Local::now().date_naive() + Duration::days(1);
-    let file_path = get_log_path_for_date(tomorrow, &config);
+    let file_path = get_log_path_for_date(tomorrow, &config).unwrap();
 
     // Create parent directory
     if let Some(parent) = file_path.parent() {
@@ -101,7 +110,7 @@ fn test_edit_nonexistent_file() {
     env::set_var("EDITOR", "echo");
 
     // Test editing a non-existent file
-    edit_log_for_day(-1, &config, false); // -1 means tomorrow now
+    edit_log_for_day(-1, &config, None).unwrap(); // -1 means tomorrow now
 
     // Verify the file was created with template content
     assert!(file_path.exists());
@@ -113,7 +122,7 @@ fn test_edit_nonexistent_file() {
 fn test_edit_past_date_does_not_create_file() {
     let (_temp_dir, config) = setup_test_env();
     let two_days_ago = Local::now().date_naive() - Duration::days(2);
-    let file_path = get_log_path_for_date(two_days_ago, &config);
+    let file_path = get_log_path_for_date(two_days_ago, &config).unwrap();
 
     // Create parent directory
     if let Some(parent) = file_path.parent() {
@@ -124,7 +133,7 @@ fn test_edit_past_date_does_not_create_file() {
     env::set_var("EDITOR", "echo");
 
     // Test editing a non-existent past file
-    edit_log_for_day(2, &config, false);
+    edit_log_for_day(2, &config, None).unwrap();
 
     // Verify the file was NOT created
     assert!(!file_path.exists());
@@ -134,7 +143,7 @@ fn test_edit_past_date_does_not_create_file() {
 fn test_edit_future_date_creates_file() {
     let (_temp_dir, config) = setup_test_env();
     let tomorrow = Local::now().date_naive() + Duration::days(1);
-    let file_path = get_log_path_for_date(tomorrow, &config);
+    let file_path = get_log_path_for_date(tomorrow, &config).unwrap();
 
     // Create parent directory
     if let Some(parent) = file_path.parent() {
@@ -145,7 +154,7 @@ fn test_edit_future_date_creates_file() {
     env::set_var("EDITOR", "echo");
 
     // Test editing a non-existent future file
-    edit_log_for_day(-1, &config, false);
+    edit_log_for_day(-1, &config, None).unwrap();
 
     // Verify the file was created with template content
     assert!(file_path.exists());
@@ -0,0 +1,147 @@
+use chrono::NaiveTime;
+use obsidian_logging::config::{Config, ListType, TimeFormat};
+use obsidian_logging::document::{DailyLog, TaskStatus};
+use obsidian_logging::EntryFormat;
+
+fn create_test_config(entry_format: &str) -> Config {
+    Config {
+        vault: "/test/vault".to_string(),
+        file_path_format: "{date}.md".to_string(),
+        section_header: "## Log".to_string(),
+        list_type: ListType::Custom,
+        template_path: None,
+        locale: None,
+        time_format: TimeFormat::Hour24,
+        time_label: "Time".to_string(),
+        event_label: "Event".to_string(),
+        category_headers: std::collections::HashMap::new(),
+        phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: Some(EntryFormat::new(entry_format).unwrap()),
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
+    }
+}
+
+fn time(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+#[test]
+fn test_render_and_parse_round_trip() {
+    for format in [
+        "- **{time}** — {text}",
+        "- [{time}] {text}",
+        "{n}. {time} {text}",
+        "> - {time} {text}",
+    ] {
+        let entry_format = EntryFormat::new(format).unwrap();
+        let line = entry_format.render(3, "14:30:00", "Call the bank", None);
+        assert_eq!(
+            entry_format.parse(&line),
+            Some(("14:30:00", "Call the bank", None)),
+            "{} rendered as {}",
+            format,
+            line
+        );
+    }
+
+    let numbered = EntryFormat::new("{n}. {time} {text}").unwrap();
+    assert_eq!(
+        numbered.render(2, "09:00:00", "Standup", None),
+        "2. 09:00:00 Standup"
+    );
+    assert_eq!(numbered.parse("- 09:00:00 Standup"), None);
+}
+
+#[test]
+fn test_parse_tolerates_whitespace() {
+    let format = EntryFormat::new("- **{time}** — {text}").unwrap();
+    assert_eq!(
+        format.parse("-   **9:05 AM**  —  Coffee  "),
+        Some(("9:05 AM", "Coffee", None))
+    );
+    assert_eq!(format.parse("- **later** — Coffee"), None);
+    assert_eq!(format.parse("- **09:05** — "), None);
+}
+
+#[test]
+fn test_invalid_formats() {
+    assert!(EntryFormat::new("- {text}").is_err());
+    assert!(EntryFormat::new("- {time}").is_err());
+    assert!(EntryFormat::new("- {time} {text} {text}").is_err());
+    assert!(EntryFormat::new("{n}. {n} {time} {text}").is_err());
+    let error = EntryFormat::new("- {time} {tags} {text}").unwrap_err();
+    assert!(error.contains("{tags}"), "{}", error);
+}
+
+#[test]
+fn test_tasks() {
+    let with_placeholder = EntryFormat::new("- {task} {time} {text}").unwrap();
+    let line = with_placeholder.render(1, "10:00:00", "Pay rent", Some(TaskStatus::Open));
+    assert_eq!(line, "- [ ] 10:00:00 Pay rent");
+    assert_eq!(
+        with_placeholder.parse("- [x] 10:00:00 Pay rent"),
+        Some(("10:00:00", "Pay rent", Some(TaskStatus::Done)))
+    );
+    assert_eq!(
+        with_placeholder.parse("- 10:00:00 Pay rent"),
+        Some(("10:00:00", "Pay rent", None))
+    );
+
+    let without_placeholder = EntryFormat::new("- [{time}] {text}").unwrap();
+    assert_eq!(
+        without_placeholder.render(1, "10:00:00", "Pay rent", Some(TaskStatus::Done)),
+        "- [10:00:00] [x] Pay rent"
+    );
+}
+
+#[test]
+fn test_daily_log_in_custom_format() {
+    let config = create_test_config("{n}. **{time}** — {text}");
+    let content = "# Today\n\n## Log\n\n1. **11:00:00** — Review\n2. **09:00:00** — Standup\n";
+    let mut log = DailyLog::parse(content, &config);
+
+    let section = log.section_mut("## Log").unwrap();
+    let texts: Vec<&str> = section.entries.iter().map(|e| e.text.as_str()).collect();
+    assert_eq!(texts, vec!["Review", "Standup"]);
+
+    section.insert(time(10, 0), "Coffee");
+    let task = section.insert_task(time(12, 0), "Book flights");
+    assert_eq!(task, time(12, 0));
+
+    assert_eq!(
+        log.render(&config),
+        "# Today\n\n## Log\n\n\
+         1. **09:00:00** — Standup\n\
+         2. **10:00:00** — Coffee\n\
+         3. **11:00:00** — Review\n\
+         4. **12:00:00** — [ ] Book flights\n"
+    );
+
+    let reparsed = DailyLog::parse(&log.render(&config), &config);
+    let entries = &reparsed.section("## Log").unwrap().entries;
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[3].task, Some(TaskStatus::Open));
+    assert_eq!(entries[3].text, "Book flights");
+}
+
+#[test]
+fn test_existing_sections_keep_their_format() {
+    let config = create_test_config("- [{time}] {text}");
+    let content = "## Log\n\n* 09:00:00 Standup\n";
+    let mut log = DailyLog::parse(content, &config);
+    log.section_mut("## Log")
+        .unwrap()
+        .insert(time(10, 0), "Coffee");
+
+    assert_eq!(
+        log.render(&config),
+        "## Log\n\n* 09:00:00 Standup\n* 10:00:00 Coffee\n"
+    );
+}
@@ -0,0 +1,211 @@
+use chrono::{NaiveDate, NaiveTime};
+use obsidian_logging::commands::add::add_entry_for_date;
+use obsidian_logging::commands::properties::{parse_property, set_properties};
+use obsidian_logging::config::{Config, FrontMatterConfig, ListType, TimeFormat};
+use obsidian_logging::document::DailyLog;
+use obsidian_logging::front_matter::{collect_tags, FrontMatter};
+use obsidian_logging::Error;
+use serde_yaml::Value;
+use std::fs;
+use tempfile::TempDir;
+
+fn setup_test_env() -> (TempDir, Config) {
+    let temp_dir = TempDir::new().unwrap();
+    let mut category_headers = std::collections::HashMap::new();
+    category_headers.insert("section_header_work".to_string(), "## Work".to_string());
+    let config = Config {
+        vault: temp_dir.path().to_str().unwrap().to_string(),
+        file_path_format: "{date}.md".to_string(),
+        section_header: "## Log".to_string(),
+        list_type: ListType::Bullet,
+        template_path: None,
+        locale: None,
+        time_format: TimeFormat::Hour24,
+        time_label: "Tidspunkt".to_string(),
+        event_label: "Hendelse".to_string(),
+        category_headers,
+        phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: FrontMatterConfig {
+            updated: Some("updated".to_string()),
+            entries: Some("entries".to_string()),
+            tags: Some("tags".to_string()),
+        },
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
+    };
+    (temp_dir, config)
+}
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()
+}
+
+fn add(config: &Config, time: (u32, u32), text: &str, category: Option<&str>) {
+    add_entry_for_date(
+        date(),
+        vec![text.to_string()],
+        NaiveTime::from_hms_opt(time.0, time.1, 0),
+        config,
+        true,
+        category,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_unchanged_front_matter_is_kept() {
+    let raw = "# Properties\ntags:   [daily]\nmood: \"good\"\n";
+    let front_matter = FrontMatter::parse(raw);
+    assert_eq!(front_matter.render(), raw);
+    assert_eq!(
+        front_matter.get("mood"),
+        Some(&Value::String("good".to_string()))
+    );
+}
+
+#[test]
+fn test_set_replaces_only_changed_properties() {
+    let mut front_matter = FrontMatter::parse(
+        "# Properties\ntags:\n  - daily\n\nmood: \"good\" # today\nentries: 1\n",
+    );
+    front_matter
+        .set("tags", serde_yaml::from_str("[daily, work]").unwrap())
+        .unwrap();
+    front_matter.set("entries", 2.into()).unwrap();
+    front_matter.set("energy", 4.into()).unwrap();
+
+    assert_eq!(
+        front_matter.render(),
+        "# Properties\ntags:\n- daily\n- work\n\nmood: \"good\" # today\nentries: 2\nenergy: 4\n"
+    );
+}
+
+#[test]
+fn test_invalid_front_matter_is_not_changed() {
+    let mut front_matter = FrontMatter::parse("tags: [daily\n");
+    assert!(front_matter.properties().is_none());
+    assert!(front_matter.set("mood", "good".into()).is_err());
+    assert_eq!(front_matter.render(), "tags: [daily\n");
+}
+
+#[test]
+fn test_collect_tags() {
+    assert_eq!(
+        collect_tags("Deploy #release/v2 and #ops, not issue#4 or #123 #ops"),
+        vec!["release/v2".to_string(), "ops".to_string()]
+    );
+}
+
+#[test]
+fn test_properties_are_maintained_when_logging() {
+    let (temp_dir, config) = setup_test_env();
+    let path = temp_dir.path().join("2024-03-15.md");
+    fs::write(
+        &path,
+        "---\ntags: daily\nmood: good\n---\n# 2024-03-15\n\n## Log\n\n* 08:00:00 Start\n",
+    )
+    .unwrap();
+
+    add(&config, (9, 0), "Fixed #bug in #billing", None);
+    add(&config, (10, 0), "Standup #meeting", Some("work"));
+
+    let content = fs::read_to_string(&path).unwrap();
+    let log = DailyLog::parse(&content, &config);
+    let front_matter = log.front_matter.as_ref().unwrap();
+    assert_eq!(front_matter.get("entries"), Some(&Value::from(2)));
+    assert_eq!(front_matter.get("entries_work"), Some(&Value::from(1)));
+    assert_eq!(
+        front_matter.get("tags"),
+        Some(&serde_yaml::from_str("[daily, bug, billing, meeting]").unwrap())
+    );
+    assert!(front_matter.get("updated").unwrap().as_str().unwrap().len() == 19);
+    assert!(content.contains("mood: good\n"));
+    assert!(content.contains("* 09:00:00 Fixed #bug in #billing"));
+}
+
+#[test]
+fn test_invalid_front_matter_is_reported_when_logging() {
+    let (temp_dir, config) = setup_test_env();
+    let path = temp_dir.path().join("2024-03-15.md");
+    fs::write(&path, "---\ntags: [daily\n---\n## Log\n").unwrap();
+
+    let warnings = add_entry_for_date(
+        date(),
+        vec!["Start".to_string()],
+        NaiveTime::from_hms_opt(9, 0, 0),
+        &config,
+        true,
+        None,
+    )
+    .unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].path, path);
+    assert!(warnings[0].message.starts_with("front matter not updated"));
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .starts_with("---\ntags: [daily\n---\n"));
+}
+
+#[test]
+fn test_front_matter_is_added_to_new_notes() {
+    let (temp_dir, mut config) = setup_test_env();
+    config.front_matter.updated = None;
+    add(&config, (9, 0), "First", None);
+
+    let content = fs::read_to_string(temp_dir.path().join("2024-03-15.md")).unwrap();
+    assert!(content.starts_with("---\nentries: 1\n---\n"));
+}
+
+#[test]
+fn test_set_properties() {
+    let (temp_dir, mut config) = setup_test_env();
+    config.front_matter = FrontMatterConfig::default();
+    let path = temp_dir.path().join("2024-03-15.md");
+    fs::write(&path, "---\nmood: bad\n---\n## Log\n\n* 08:00:00 Start\n").unwrap();
+
+    let properties = vec![
+        parse_property("mood=good").unwrap(),
+        parse_property("energy=4").unwrap(),
+        parse_property("people=[Ann, Bo]").unwrap(),
+    ];
+    set_properties(date(), &properties, &config, true, None).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "---\nmood: good\nenergy: 4\npeople:\n- Ann\n- Bo\n---\n## Log\n\n* 08:00:00 Start\n"
+    );
+
+    let properties = vec![
+        parse_property("topic=#work").unwrap(),
+        parse_property("note=a: b").unwrap(),
+        parse_property("reviewed=true").unwrap(),
+        parse_property("ratio=0.5").unwrap(),
+    ];
+    assert_eq!(
+        properties,
+        vec![
+            ("topic".to_string(), Value::String("#work".to_string())),
+            ("note".to_string(), Value::String("a: b".to_string())),
+            ("reviewed".to_string(), Value::Bool(true)),
+            ("ratio".to_string(), Value::Number(0.5.into())),
+        ]
+    );
+    set_properties(date(), &properties, &config, true, None).unwrap();
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .contains("topic: '#work'\nnote: 'a: b'\nreviewed: true\nratio: 0.5\n"));
+
+    fs::write(&path, "---\nmood: [bad\n---\n").unwrap();
+    let result = set_properties(date(), &properties, &config, true, None);
+    assert!(matches!(result, Err(Error::FrontMatter { .. })));
+    assert!(matches!(
+        parse_property("mood"),
+        Err(Error::InvalidEntry(_))
+    ));
+}
@@ -0,0 +1,103 @@
+use chrono::Locale;
+use obsidian_logging::config::Config;
+use obsidian_logging::i18n::Messages;
+use obsidian_logging::Error;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_builtin_messages() {
+    let german = Messages::builtin(Locale::de_AT);
+    assert_eq!(german.get("time_label"), "Zeit");
+    assert_eq!(german.get("conjunction"), "und");
+    assert_eq!(
+        german.format("no_log_found", &[("date", &"2026-10-16")]),
+        "Kein Protokoll für 2026-10-16 gefunden"
+    );
+
+    let english = Messages::builtin(Locale::en_GB);
+    assert_eq!(english.get("event_label"), "Event");
+    assert_eq!(
+        english.format("changed", &[("old", &"a"), ("new", &"b")]),
+        "Changed: a -> b"
+    );
+
+    // Languages without a bundle use English
+    assert_eq!(Messages::builtin(Locale::el_GR).get("logged"), "Logged.");
+    assert_eq!(Messages::builtin(Locale::nb_NO).get("conjunction"), "og");
+    assert_eq!(english.get("unknown_key"), "unknown_key");
+}
+
+#[test]
+fn test_conjunction_follows_locale() {
+    let mut config = Config::default();
+    assert_eq!(config.get_conjunction(), "and");
+    for (tag, expected) in [
+        ("nb", "og"),
+        ("nb_NO", "og"),
+        ("nn_NO", "og"),
+        ("de-AT", "und"),
+        ("sv_SE", "och"),
+    ] {
+        config.locale = Some(tag.to_string());
+        assert_eq!(config.get_conjunction(), expected, "{}", tag);
+    }
+}
+
+#[test]
+fn test_override_files() {
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("de.yaml"),
+        "logged: Notiert.\ntime_label: Uhrzeit\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("de_AT.yaml"), "logged: Eingetragen.\n").unwrap();
+
+    let messages = Messages::load(Locale::de_AT, Some(dir.path())).unwrap();
+    assert_eq!(messages.get("logged"), "Eingetragen.");
+    assert_eq!(messages.get("time_label"), "Uhrzeit");
+    assert_eq!(messages.get("event_label"), "Ereignis");
+
+    let messages = Messages::load(Locale::de_DE, Some(dir.path())).unwrap();
+    assert_eq!(messages.get("logged"), "Notiert.");
+
+    fs::write(dir.path().join("de.yaml"), "logged: [Notiert\n").unwrap();
+    assert!(matches!(
+        Messages::load(Locale::de_DE, Some(dir.path())),
+        Err(Error::ConfigParse { .. })
+    ));
+}
+
+#[test]
+fn test_labels_follow_locale() {
+    let dir = TempDir::new().unwrap();
+    let translations = dir.path().join("translations");
+    fs::create_dir(&translations).unwrap();
+    fs::write(translations.join("sv.yaml"), "event_label: Anteckning\n").unwrap();
+    let config_path = dir.path().join("config.yaml");
+    let write_config = |extra: &str| {
+        fs::write(
+            &config_path,
+            format!("translations_dir: {}\n{}", translations.display(), extra),
+        )
+        .unwrap();
+    };
+
+    write_config("");
+    let config = Config::load_layered(std::slice::from_ref(&config_path)).unwrap();
+    assert_eq!(config.time_label, "Time");
+    assert_eq!(config.event_label, "Event");
+
+    write_config("locale: sv_SE\n");
+    let config = Config::load_layered(std::slice::from_ref(&config_path)).unwrap();
+    assert_eq!(config.time_label, "Tidpunkt");
+    assert_eq!(config.event_label, "Anteckning");
+
+    write_config("locale: sv_SE\nevent_label: Vad\n");
+    let config = Config::load_layered(std::slice::from_ref(&config_path)).unwrap();
+    assert_eq!(config.event_label, "Vad");
+
+    let config: Config = serde_yaml::from_str("locale: nb_NO\n").unwrap();
+    assert_eq!(config.time_label, "Tidspunkt");
+}
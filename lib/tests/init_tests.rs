@@ -0,0 +1,151 @@
+use obsidian_logging::config::{unknown_keys, Config, ListType};
+use obsidian_logging::init::{prompt_settings, render_config, run_init, InitSettings};
+use std::fs;
+use std::io::Cursor;
+use tempfile::TempDir;
+
+fn answers(lines: &[&str]) -> Cursor<Vec<u8>> {
+    Cursor::new(format!("{}\n", lines.join("\n")).into_bytes())
+}
+
+#[test]
+fn test_init_writes_config_and_template() {
+    let temp_dir = TempDir::new().unwrap();
+    let vault = temp_dir.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    let config_path = temp_dir.path().join("config").join("obsidian-logging.yaml");
+    let template_path = temp_dir.path().join("templates").join("daily.md");
+
+    let mut input = answers(&[
+        vault.to_str().unwrap(),
+        "Journal/{date}.md",
+        "## Log",
+        "table",
+        "nb_NO",
+        template_path.to_str().unwrap(),
+        "",
+    ]);
+    let mut output = Vec::new();
+    assert!(run_init(&config_path, &mut input, &mut output).unwrap());
+
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(unknown_keys(&content).is_empty());
+    let config = Config::from_yaml(&content, &config_path).unwrap();
+    assert_eq!(
+        config.vault,
+        fs::canonicalize(&vault).unwrap().to_str().unwrap()
+    );
+    assert_eq!(config.file_path_format, "Journal/{date}.md");
+    assert_eq!(config.section_header, "## Log");
+    assert_eq!(config.list_type, ListType::Table);
+    assert_eq!(config.locale.as_deref(), Some("nb_NO"));
+    assert_eq!(
+        config.template_path.as_deref(),
+        Some(template_path.to_str().unwrap())
+    );
+    assert!(config.problems().is_empty());
+
+    let template = fs::read_to_string(&template_path).unwrap();
+    assert!(template.contains("{today}"));
+    assert!(template.ends_with("## Log\n\n"));
+}
+
+#[test]
+fn test_init_asks_again_for_invalid_answers() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("new-vault");
+
+    let mut input = answers(&[
+        missing.to_str().unwrap(),
+        "y",
+        "Journal/{year}.md",
+        "Journal/{date}.txt",
+        "",
+        "Log",
+        "",
+        "list",
+        "custom",
+        "bullet",
+        "xx_XX",
+        "",
+        "",
+    ]);
+    let mut output = Vec::new();
+    let settings = prompt_settings(&mut input, &mut output).unwrap();
+
+    assert!(missing.is_dir());
+    assert_eq!(
+        settings,
+        InitSettings {
+            vault: fs::canonicalize(&missing)
+                .unwrap()
+                .to_string_lossy()
+                .into_owned(),
+            file_path_format: Config::default().file_path_format,
+            section_header: "## 🕗".to_string(),
+            list_type: ListType::Bullet,
+            locale: None,
+            template_path: None,
+            create_template: false,
+        }
+    );
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("The path must identify the day"));
+    assert!(output.contains("The path must end with .md."));
+    assert!(output.contains("must be a markdown heading"));
+    assert_eq!(output.matches("Expected 'bullet' or 'table'.").count(), 2);
+    assert!(output.contains("Unknown locale 'xx_XX'"));
+}
+
+#[test]
+fn test_init_writes_the_vault_as_an_absolute_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let vault = temp_dir.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    let indirect = temp_dir.path().join("other").join("..").join("vault");
+    fs::create_dir_all(temp_dir.path().join("other")).unwrap();
+
+    let mut input = answers(&[indirect.to_str().unwrap(), "", "", "", "", ""]);
+    let settings = prompt_settings(&mut input, &mut Vec::new()).unwrap();
+
+    // A relative vault would be resolved against the config file's directory
+    assert_eq!(
+        settings.vault,
+        fs::canonicalize(&vault).unwrap().to_string_lossy()
+    );
+    assert!(std::path::Path::new(&settings.vault).is_absolute());
+}
+
+#[test]
+fn test_init_keeps_existing_config_unless_confirmed() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("obsidian-logging.yaml");
+    fs::write(&config_path, "vault: /tmp\n").unwrap();
+
+    let mut output = Vec::new();
+    assert!(!run_init(&config_path, &mut answers(&["n"]), &mut output).unwrap());
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), "vault: /tmp\n");
+
+    // Running out of answers cancels the setup without writing anything
+    assert!(run_init(&config_path, &mut answers(&["y"]), &mut output).is_err());
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), "vault: /tmp\n");
+}
+
+#[test]
+fn test_render_config_quotes_values() {
+    let settings = InitSettings {
+        vault: "C:\\Users\\me\\\"vault\"".to_string(),
+        file_path_format: "{date}.md".to_string(),
+        section_header: "## 🕗".to_string(),
+        list_type: ListType::Bullet,
+        locale: None,
+        template_path: None,
+        create_template: false,
+    };
+    let content = render_config(&settings);
+    let config = Config::from_yaml(&content, std::path::Path::new("test.yaml")).unwrap();
+    assert_eq!(config.vault, settings.vault);
+    assert_eq!(config.section_header, "## 🕗");
+    assert_eq!(config.locale, None);
+    assert!(content.contains("# locale: \"en_US\""));
+}
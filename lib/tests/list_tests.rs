@@ -1,6 +1,8 @@
 use chrono::{Duration, Local};
-use obsidian_logging::commands::list::list_log_for_day;
+use obsidian_logging::commands::list::{list_log_for_day, list_log_for_range, TaskFilter};
 use obsidian_logging::config::{Config, ListType, TimeFormat};
+use obsidian_logging::document::LogEntry;
+use obsidian_logging::OutputFormat;
 use std::fs;
 use tempfile::TempDir;
 
@@ -18,6 +20,15 @@ fn setup_test_env() -> (TempDir, Config) {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
     (temp_dir, config)
 }
@@ -39,12 +50,12 @@ fn test_list_with_time_format() {
 
     // Test with 24-hour format
     config.time_format = TimeFormat::Hour24;
-    list_log_for_day(0, &config, false, false, &[]);
+    list_log_for_day(0, &config, false, false, &[], OutputFormat::Markdown, None).unwrap();
     // Note: We can't easily test stdout directly, but the code is covered
 
     // Test with 12-hour format
     config.time_format = TimeFormat::Hour12;
-    list_log_for_day(0, &config, false, false, &[]);
+    list_log_for_day(0, &config, false, false, &[], OutputFormat::Markdown, None).unwrap();
 }
 
 #[test]
@@ -67,12 +78,12 @@ fn test_list_with_table_format() {
     // Test with 24-hour format and table
     config.time_format = TimeFormat::Hour24;
     config.list_type = ListType::Table;
-    list_log_for_day(0, &config, false, false, &[]);
+    list_log_for_day(0, &config, false, false, &[], OutputFormat::Markdown, None).unwrap();
 
     // Test with 12-hour format and table
     config.time_format = TimeFormat::Hour12;
     config.list_type = ListType::Table;
-    list_log_for_day(0, &config, false, false, &[]);
+    list_log_for_day(0, &config, false, false, &[], OutputFormat::Markdown, None).unwrap();
 }
 
 #[test]
@@ -90,7 +101,7 @@ fn test_list_past_date() {
     fs::write(&file_path, content).unwrap();
 
     // Test listing a past date
-    list_log_for_day(2, &config, false, false, &[]);
+    list_log_for_day(2, &config, false, false, &[], OutputFormat::Markdown, None).unwrap();
     // Note: We can't easily test stdout directly, but the code is covered
 }
 
@@ -109,7 +120,7 @@ fn test_list_future_date() {
     fs::write(&file_path, content).unwrap();
 
     // Test listing a future date
-    list_log_for_day(-1, &config, false, false, &[]);
+    list_log_for_day(-1, &config, false, false, &[], OutputFormat::Markdown, None).unwrap();
     // Note: We can't easily test stdout directly, but the code is covered
 }
 
@@ -125,6 +136,75 @@ fn test_list_nonexistent_date() {
     }
 
     // Test listing a non-existent date
-    list_log_for_day(2, &config, false, false, &[]);
+    list_log_for_day(2, &config, false, false, &[], OutputFormat::Markdown, None).unwrap();
     // Note: We can't easily test stdout directly, but the code is covered
 }
+
+#[test]
+fn test_list_range() {
+    let (temp_dir, config) = setup_test_env();
+    let today = Local::now().date_naive();
+
+    // Notes for today and three days ago, nothing in between
+    for days_ago in [0, 3] {
+        let date = today - Duration::days(days_ago);
+        let content = "## Test\n* 09:00 Entry\n";
+        fs::write(temp_dir.path().join(format!("{}.md", date)), content).unwrap();
+    }
+
+    list_log_for_range(
+        today - Duration::days(7),
+        today,
+        &config,
+        false,
+        false,
+        &[],
+        OutputFormat::Markdown,
+        None,
+    )
+    .unwrap();
+    list_log_for_range(
+        today - Duration::days(7),
+        today,
+        &config,
+        false,
+        false,
+        &["all".to_string()],
+        OutputFormat::Markdown,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_list_range_rejects_reversed_dates() {
+    let (_temp_dir, config) = setup_test_env();
+    let today = Local::now().date_naive();
+
+    let result = list_log_for_range(
+        today,
+        today - Duration::days(1),
+        &config,
+        true,
+        false,
+        &[],
+        OutputFormat::Markdown,
+        None,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_task_filter() {
+    let open = LogEntry::parse("- [ ] 09:00:00 Pay rent", None).unwrap();
+    let done = LogEntry::parse("* [X] 10:00:00 Call the bank", None).unwrap();
+    let plain = LogEntry::parse("- 11:00:00 Lunch", None).unwrap();
+
+    let filter: TaskFilter = "open".parse().unwrap();
+    assert!(filter.matches(&open));
+    assert!(!filter.matches(&done));
+    assert!(TaskFilter::Done.matches(&done));
+    assert!(TaskFilter::All.matches(&open) && TaskFilter::All.matches(&done));
+    assert!(!TaskFilter::All.matches(&plain));
+    assert!("later".parse::<TaskFilter>().is_err());
+}
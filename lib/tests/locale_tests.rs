@@ -0,0 +1,63 @@
+use chrono::{Locale, NaiveDate};
+use obsidian_logging::config::Config;
+use obsidian_logging::locale::{
+    language, long_date, month_name, parse_locale, resolve_locale, short_date, weekday_name,
+};
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()
+}
+
+#[test]
+fn test_parse_posix_and_bcp47_tags() {
+    assert_eq!(parse_locale("nb_NO"), Some(Locale::nb_NO));
+    assert_eq!(parse_locale("nb_NO.UTF-8"), Some(Locale::nb_NO));
+    assert_eq!(parse_locale("en-GB"), Some(Locale::en_GB));
+    assert_eq!(parse_locale("sv-se"), Some(Locale::sv_SE));
+    assert_eq!(parse_locale("sr-Latn-RS"), Some(Locale::sr_RS_latin));
+    assert_eq!(parse_locale("sr_RS@latin"), Some(Locale::sr_RS_latin));
+}
+
+#[test]
+fn test_language_fallback() {
+    assert_eq!(parse_locale("da"), Some(Locale::da_DK));
+    assert_eq!(parse_locale("no"), Some(Locale::nb_NO));
+    assert_eq!(parse_locale("de"), Some(Locale::de_DE));
+    assert_eq!(parse_locale("es-419"), Some(Locale::es_ES));
+    assert_eq!(parse_locale("en-XX"), Some(Locale::en_US));
+    assert_eq!(parse_locale("xx_YY"), None);
+
+    assert_eq!(resolve_locale(Some("xx_YY")), Locale::en_US);
+    assert_eq!(resolve_locale(None), Locale::en_US);
+    assert_eq!(language(Locale::nb_NO), "nb");
+}
+
+#[test]
+fn test_localized_names_and_dates() {
+    assert_eq!(weekday_name(date(), Locale::nn_NO), "fredag");
+    assert_eq!(month_name(date(), Locale::sv_SE), "oktober");
+    assert_eq!(long_date(date(), Locale::en_US), "Friday, October 16, 2026");
+    assert_eq!(long_date(date(), Locale::en_GB), "Friday 16 October 2026");
+    assert_eq!(long_date(date(), Locale::nb_NO), "fredag 16. oktober 2026");
+    assert_eq!(long_date(date(), Locale::nn_NO), "fredag 16. oktober 2026");
+    assert_eq!(long_date(date(), Locale::ja_JP), "2026年10月16日 金曜日");
+    assert_eq!(short_date(date(), Locale::de_DE), "16.10.2026");
+    assert_eq!(short_date(date(), Locale::en_US), "10/16/2026");
+}
+
+#[test]
+fn test_unknown_locale_is_reported() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = Config {
+        vault: dir.path().to_str().unwrap().to_string(),
+        locale: Some("nb-NO".to_string()),
+        ..Config::default()
+    };
+    assert!(config.problems().is_empty());
+
+    config.locale = Some("klingon".to_string());
+    assert_eq!(
+        config.problems(),
+        vec!["locale 'klingon' is not known, English is used".to_string()]
+    );
+}
@@ -0,0 +1,84 @@
+use chrono::{NaiveDate, NaiveTime};
+use obsidian_logging::document::TaskStatus;
+use obsidian_logging::output::{render_records, EntryRecord, OutputFormat};
+use std::str::FromStr;
+
+fn records() -> Vec<EntryRecord> {
+    vec![
+        EntryRecord {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            category: None,
+            section: "## 🕗".to_string(),
+            text: "Standup".to_string(),
+            task: None,
+        },
+        EntryRecord {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            time: NaiveTime::from_hms_opt(14, 30, 0).unwrap(),
+            category: Some("work".to_string()),
+            section: "## Work".to_string(),
+            text: "Said \"hi\", then\tleft".to_string(),
+            task: Some(TaskStatus::Done),
+        },
+    ]
+}
+
+#[test]
+fn test_parse_output_format() {
+    assert_eq!(OutputFormat::from_str("JSON"), Ok(OutputFormat::Json));
+    assert_eq!(OutputFormat::from_str("jsonl"), Ok(OutputFormat::JsonLines));
+    assert_eq!(OutputFormat::from_str("csv"), Ok(OutputFormat::Csv));
+    assert_eq!(OutputFormat::from_str("tsv"), Ok(OutputFormat::Tsv));
+    assert!(OutputFormat::from_str("xml").is_err());
+    assert_eq!(OutputFormat::default(), OutputFormat::Markdown);
+}
+
+#[test]
+fn test_render_json() {
+    let output = render_records(&records(), OutputFormat::Json);
+    let parsed: Vec<serde_yaml::Value> = serde_yaml::from_str(&output).unwrap();
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0]["date"], "2024-01-15");
+    assert_eq!(parsed[0]["time"], "09:00:00");
+    assert!(parsed[0]["category"].is_null());
+    assert_eq!(parsed[1]["category"], "work");
+    assert_eq!(parsed[1]["section"], "## Work");
+    assert_eq!(parsed[1]["task"], "done");
+
+    assert_eq!(render_records(&[], OutputFormat::Json), "[]\n");
+}
+
+#[test]
+fn test_render_json_lines() {
+    let output = render_records(&records(), OutputFormat::JsonLines);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0],
+        r###"{"date":"2024-01-15","time":"09:00:00","category":null,"section":"## 🕗","text":"Standup"}"###
+    );
+    assert!(render_records(&[], OutputFormat::JsonLines).is_empty());
+}
+
+#[test]
+fn test_render_csv_quotes_fields() {
+    let output = render_records(&records(), OutputFormat::Csv);
+    assert_eq!(
+        output,
+        "date,time,category,section,text\n\
+         2024-01-15,09:00:00,,## 🕗,Standup\n\
+         2024-01-15,14:30:00,work,## Work,\"Said \"\"hi\"\", then\tleft\"\n"
+    );
+}
+
+#[test]
+fn test_render_tsv_escapes_tabs() {
+    let output = render_records(&records(), OutputFormat::Tsv);
+    assert_eq!(
+        output,
+        "date\ttime\tcategory\tsection\ttext\n\
+         2024-01-15\t09:00:00\t\t## 🕗\tStandup\n\
+         2024-01-15\t14:30:00\twork\t## Work\tSaid \"hi\", then\\tleft\n"
+    );
+}
@@ -0,0 +1,371 @@
+use chrono::{Local, NaiveDate, NaiveTime};
+use obsidian_logging::commands::add::add_entry_for_date;
+use obsidian_logging::commands::amend::{amend_entry, Amendment, EntrySelector};
+use obsidian_logging::commands::edit::edit_log_for_day;
+use obsidian_logging::commands::search::{search_entries, SearchOptions};
+use obsidian_logging::config::{Config, ListType, Period, PeriodicNote, TimeFormat};
+use obsidian_logging::utils::{get_log_path_for_category, parse_log_path};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use tempfile::TempDir;
+
+fn setup_test_env() -> (TempDir, Config) {
+    let temp_dir = TempDir::new().unwrap();
+    let mut category_headers = HashMap::new();
+    category_headers.insert("section_header_retro".to_string(), "## Retro".to_string());
+    category_headers.insert(
+        "section_header_one_on_one".to_string(),
+        "## 1:1".to_string(),
+    );
+    category_headers.insert("section_header_work".to_string(), "## Work".to_string());
+
+    let template_path = temp_dir.path().join("weekly-template.md");
+    fs::write(&template_path, "# Week of {today}\n\n## Retro\n\n").unwrap();
+
+    let mut periodic_notes = HashMap::new();
+    periodic_notes.insert(
+        Period::Weekly,
+        PeriodicNote {
+            file_path_format: "Weekly/{week_year}-W{week}.md".to_string(),
+            template_path: Some(template_path.to_str().unwrap().to_string()),
+            categories: vec!["retro".to_string()],
+        },
+    );
+    periodic_notes.insert(
+        Period::Monthly,
+        PeriodicNote {
+            file_path_format: "Monthly/{year}-{month}.md".to_string(),
+            template_path: None,
+            categories: vec!["one_on_one".to_string()],
+        },
+    );
+
+    let config = Config {
+        vault: temp_dir.path().to_str().unwrap().to_string(),
+        file_path_format: "Daily/{date}.md".to_string(),
+        section_header: "## Log".to_string(),
+        list_type: ListType::Bullet,
+        template_path: None,
+        locale: None,
+        time_format: TimeFormat::Hour24,
+        time_label: "Tidspunkt".to_string(),
+        event_label: "Hendelse".to_string(),
+        category_headers,
+        phrases: HashMap::new(),
+        profile: None,
+        periodic_notes,
+        template_variables: HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
+    };
+    (temp_dir, config)
+}
+
+fn time(hour: u32) -> Option<NaiveTime> {
+    NaiveTime::from_hms_opt(hour, 0, 0)
+}
+
+#[test]
+fn test_period_start() {
+    let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+    assert_eq!(Period::Daily.start(date), date);
+    assert_eq!(
+        Period::Weekly.start(date),
+        NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+    );
+    assert_eq!(
+        Period::Monthly.start(date),
+        NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()
+    );
+    assert_eq!(
+        Period::Yearly.start(date),
+        NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+    );
+}
+
+#[test]
+fn test_categories_are_added_to_periodic_notes() {
+    let (temp_dir, config) = setup_test_env();
+    let friday = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+    let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+
+    for (date, category, hour, text) in [
+        (friday, Some("retro"), 15, "Less meetings"),
+        (monday, Some("retro"), 9, "Ship smaller PRs"),
+        (friday, Some("one_on_one"), 11, "Career talk"),
+        (friday, Some("work"), 10, "Deploy"),
+        (friday, None, 8, "Coffee"),
+    ] {
+        add_entry_for_date(
+            date,
+            vec![text.to_string()],
+            time(hour),
+            &config,
+            true,
+            category,
+        )
+        .unwrap();
+    }
+
+    let weekly = temp_dir.path().join("Weekly").join("2026-W42.md");
+    assert_eq!(
+        fs::read_to_string(&weekly).unwrap(),
+        "# Week of 2026-10-12\n\n## Retro\n\n* 2026-10-12 09:00:00 Ship smaller PRs\n* 2026-10-16 15:00:00 Less meetings\n"
+    );
+    assert_eq!(
+        get_log_path_for_category(monday, &config, Some("retro")).unwrap(),
+        weekly
+    );
+
+    // Without a template the monthly note only holds the section
+    let monthly = temp_dir.path().join("Monthly").join("2026-10.md");
+    assert_eq!(
+        fs::read_to_string(monthly).unwrap(),
+        "## 1:1\n\n* 2026-10-16 11:00:00 Career talk\n"
+    );
+
+    let daily = fs::read_to_string(temp_dir.path().join("Daily").join("2026-10-16.md")).unwrap();
+    assert!(daily.contains("* 08:00:00 Coffee"));
+    assert!(daily.contains("* 10:00:00 Deploy"));
+    assert!(!daily.contains("Retro"));
+}
+
+#[test]
+fn test_periodic_entries_are_sorted_by_day_and_time() {
+    let (temp_dir, config) = setup_test_env();
+    let day = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+    let at = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0);
+
+    for (date, time, text) in [
+        (day(16), at(9, 0), "Friday morning"),
+        (day(12), at(17, 0), "Monday afternoon"),
+        (day(12), at(21, 5), "Monday evening"),
+        (day(15), at(21, 5), "Thursday evening"),
+    ] {
+        add_entry_for_date(
+            date,
+            vec![text.to_string()],
+            time,
+            &config,
+            true,
+            Some("retro"),
+        )
+        .unwrap();
+    }
+
+    // The same time on another day is not a collision
+    let weekly = temp_dir.path().join("Weekly").join("2026-W42.md");
+    assert_eq!(
+        fs::read_to_string(&weekly).unwrap(),
+        "# Week of 2026-10-12\n\n## Retro\n\n\
+         * 2026-10-12 17:00:00 Monday afternoon\n\
+         * 2026-10-12 21:05:00 Monday evening\n\
+         * 2026-10-15 21:05:00 Thursday evening\n\
+         * 2026-10-16 09:00:00 Friday morning\n"
+    );
+
+    // A time selects the entry of the given day
+    amend_entry(
+        day(15),
+        EntrySelector::Time(at(21, 5).unwrap()),
+        Amendment::Update {
+            text: Some("Thursday night".to_string()),
+            time: None,
+        },
+        &config,
+        true,
+        Some("retro"),
+    )
+    .unwrap();
+    let content = fs::read_to_string(&weekly).unwrap();
+    assert!(content.contains("* 2026-10-12 21:05:00 Monday evening\n"));
+    assert!(content.contains("* 2026-10-15 21:05:00 Thursday night\n"));
+
+    // Search finds entries of periodic notes, dated by their day
+    let (matches, _) = search_entries("day", &SearchOptions::default(), &config).unwrap();
+    let found: Vec<(NaiveDate, &str)> = matches
+        .iter()
+        .map(|m| (m.date, m.entry.text.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (day(16), "Friday morning"),
+            (day(15), "Thursday night"),
+            (day(12), "Monday evening"),
+            (day(12), "Monday afternoon"),
+        ]
+    );
+}
+
+#[test]
+fn test_amend_entry_in_periodic_note() {
+    let (temp_dir, config) = setup_test_env();
+    let wednesday = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+    add_entry_for_date(
+        wednesday,
+        vec!["Less meetigns".to_string()],
+        time(15),
+        &config,
+        true,
+        Some("retro"),
+    )
+    .unwrap();
+
+    amend_entry(
+        wednesday,
+        EntrySelector::Last,
+        Amendment::Update {
+            text: Some("Less meetings".to_string()),
+            time: None,
+        },
+        &config,
+        true,
+        Some("retro"),
+    )
+    .unwrap();
+
+    let weekly = temp_dir.path().join("Weekly").join("2026-W42.md");
+    assert!(fs::read_to_string(weekly)
+        .unwrap()
+        .contains("* 2026-10-14 15:00:00 Less meetings\n"));
+}
+
+#[test]
+fn test_amend_counts_entries_of_the_day_in_periodic_note() {
+    let (temp_dir, config) = setup_test_env();
+    let day = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+    for (date, hour, text) in [
+        (day(12), 9, "Monday"),
+        (day(14), 9, "Wednesday morning"),
+        (day(14), 15, "Wednesday afternoon"),
+        (day(16), 9, "Friday"),
+    ] {
+        add_entry_for_date(
+            date,
+            vec![text.to_string()],
+            time(hour),
+            &config,
+            true,
+            Some("retro"),
+        )
+        .unwrap();
+    }
+
+    amend_entry(
+        day(14),
+        EntrySelector::Index(1),
+        Amendment::Remove,
+        &config,
+        true,
+        Some("retro"),
+    )
+    .unwrap();
+    amend_entry(
+        day(14),
+        EntrySelector::Last,
+        Amendment::Update {
+            text: Some("Wednesday retro".to_string()),
+            time: None,
+        },
+        &config,
+        true,
+        Some("retro"),
+    )
+    .unwrap();
+
+    let weekly = temp_dir.path().join("Weekly").join("2026-W42.md");
+    assert!(fs::read_to_string(&weekly).unwrap().ends_with(
+        "* 2026-10-12 09:00:00 Monday
+* 2026-10-14 15:00:00 Wednesday retro
+* 2026-10-16 09:00:00 Friday
+"
+    ));
+
+    // Entries of other days cannot be selected
+    let result = amend_entry(
+        day(14),
+        EntrySelector::Index(2),
+        Amendment::Remove,
+        &config,
+        true,
+        Some("retro"),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_edit_creates_the_current_periodic_note() {
+    let (_temp_dir, config) = setup_test_env();
+    env::set_var("EDITOR", "true");
+
+    let today = Local::now().date_naive();
+    edit_log_for_day(0, &config, Some("one_on_one")).unwrap();
+    assert!(
+        get_log_path_for_category(today, &config, Some("one_on_one"))
+            .unwrap()
+            .exists()
+    );
+    assert!(!get_log_path_for_category(today, &config, None)
+        .unwrap()
+        .exists());
+
+    // Notes for earlier periods are not created
+    edit_log_for_day(400, &config, Some("one_on_one")).unwrap();
+    let last_year = today - chrono::Duration::days(400);
+    assert!(
+        !get_log_path_for_category(last_year, &config, Some("one_on_one"))
+            .unwrap()
+            .exists()
+    );
+}
+
+#[test]
+fn test_periodic_note_paths_are_recognised() {
+    let (_temp_dir, mut config) = setup_test_env();
+    config.file_path_format = "Weekly/{week_year}-W{week}.md".to_string();
+    assert_eq!(
+        parse_log_path("Weekly/2027-W01.md", &config),
+        NaiveDate::from_ymd_opt(2027, 1, 4)
+    );
+    config.file_path_format = "Monthly/{year}-{month}.md".to_string();
+    assert_eq!(
+        parse_log_path("Monthly/2026-10.md", &config),
+        NaiveDate::from_ymd_opt(2026, 10, 1)
+    );
+}
+
+#[test]
+fn test_periodic_note_problems() {
+    let (temp_dir, mut config) = setup_test_env();
+    assert_eq!(config.problems(), Vec::<String>::new());
+
+    config.periodic_notes.insert(
+        Period::Yearly,
+        PeriodicNote {
+            file_path_format: "Yearly/{year}-{month}.md".to_string(),
+            template_path: Some(
+                temp_dir
+                    .path()
+                    .join("missing.md")
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            ),
+            categories: vec!["retro".to_string(), "goals".to_string()],
+        },
+    );
+    let problems = config.problems();
+    assert_eq!(problems.len(), 4, "{:?}", problems);
+    assert!(problems[0].contains(
+        "periodic_notes.yearly: file_path_format 'Yearly/{year}-{month}.md' does not identify the year"
+    ));
+    assert!(problems[1].starts_with("periodic_notes.yearly.template_path"));
+    assert!(problems[2].contains("category 'retro' is already logged to the weekly note"));
+    assert!(problems[3].contains("category 'goals' has no section_header_goals"));
+}
@@ -0,0 +1,170 @@
+use chrono::{NaiveDate, NaiveTime};
+use obsidian_logging::commands::search::{search_entries, SearchOptions};
+use obsidian_logging::config::{Config, ListType, TimeFormat};
+use obsidian_logging::Error;
+use std::fs;
+use tempfile::TempDir;
+
+fn setup_test_env() -> (TempDir, Config) {
+    let temp_dir = TempDir::new().unwrap();
+    let mut category_headers = std::collections::HashMap::new();
+    category_headers.insert("section_header_work".to_string(), "## Work".to_string());
+    let config = Config {
+        vault: temp_dir.path().to_str().unwrap().to_string(),
+        file_path_format: "Journal/{year}/{date}.md".to_string(),
+        section_header: "## Test".to_string(),
+        list_type: ListType::Bullet,
+        template_path: None,
+        locale: None,
+        time_format: TimeFormat::Hour24,
+        time_label: "Tidspunkt".to_string(),
+        event_label: "Hendelse".to_string(),
+        category_headers,
+        phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
+    };
+
+    let journal = temp_dir.path().join("Journal");
+    fs::create_dir_all(journal.join("2024")).unwrap();
+    fs::write(
+        journal.join("2024").join("2024-03-14.md"),
+        "## Test\n* 08:00:00 Coffee\n\n## Work\n* 10:00:00 Deployed billing service\n",
+    )
+    .unwrap();
+    fs::write(
+        journal.join("2024").join("2024-03-15.md"),
+        "## Test\n* 09:00:00 Lunch\n* 17:00:00 deployed the website\n\n## Work\n* 11:00:00 Deployed Billing hotfix\n",
+    )
+    .unwrap();
+    // Not a daily note: does not match file_path_format
+    fs::write(
+        journal.join("2024").join("notes.md"),
+        "## Test\n* 09:00:00 Deployed\n",
+    )
+    .unwrap();
+
+    (temp_dir, config)
+}
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+#[test]
+fn test_search_substring_newest_first() {
+    let (_temp_dir, config) = setup_test_env();
+
+    let (matches, _) = search_entries("Deployed", &SearchOptions::default(), &config).unwrap();
+
+    let found: Vec<(NaiveDate, &str)> = matches
+        .iter()
+        .map(|m| (m.date, m.entry.text.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (date(15), "Deployed Billing hotfix"),
+            (date(14), "Deployed billing service"),
+        ]
+    );
+    assert_eq!(matches[0].entry.category.as_deref(), Some("work"));
+    assert_eq!(
+        matches[0].entry.time,
+        NaiveTime::from_hms_opt(11, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_search_ignore_case_oldest_first_with_limit() {
+    let (_temp_dir, config) = setup_test_env();
+    let options = SearchOptions {
+        ignore_case: true,
+        oldest_first: true,
+        limit: Some(2),
+        ..SearchOptions::default()
+    };
+
+    let (matches, _) = search_entries("deployed", &options, &config).unwrap();
+
+    let found: Vec<&str> = matches.iter().map(|m| m.entry.text.as_str()).collect();
+    assert_eq!(
+        found,
+        vec!["Deployed billing service", "Deployed Billing hotfix"]
+    );
+}
+
+#[test]
+fn test_search_regex_and_category() {
+    let (_temp_dir, config) = setup_test_env();
+    let options = SearchOptions {
+        regex: true,
+        categories: vec!["work".to_string()],
+        ..SearchOptions::default()
+    };
+
+    let (matches, _) = search_entries(r"billing \w+$", &options, &config).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].date, date(14));
+
+    let options = SearchOptions {
+        regex: true,
+        ..SearchOptions::default()
+    };
+    let result = search_entries("(unclosed", &options, &config);
+    assert!(matches!(result, Err(Error::InvalidPattern { .. })));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_search_does_not_follow_symlinked_directories() {
+    let (temp_dir, config) = setup_test_env();
+    // A link back to the vault would make the walk loop if it were followed
+    std::os::unix::fs::symlink(
+        temp_dir.path(),
+        temp_dir.path().join("Journal").join("loop"),
+    )
+    .unwrap();
+
+    let (matches, _) = search_entries("Deployed", &SearchOptions::default(), &config).unwrap();
+    assert_eq!(matches.len(), 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_search_skips_unreadable_directories() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (temp_dir, config) = setup_test_env();
+    let locked = temp_dir.path().join("Private");
+    fs::create_dir(&locked).unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+    let unreadable = fs::read_dir(&locked).is_err();
+
+    let result = search_entries("Deployed", &SearchOptions::default(), &config);
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    // Permissions do not keep root out
+    if unreadable {
+        let (matches, warnings) = result.unwrap();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, locked);
+    }
+
+    // An unreadable vault is still an error
+    let mut missing = config.clone();
+    missing.vault = temp_dir
+        .path()
+        .join("missing")
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(search_entries("Deployed", &SearchOptions::default(), &missing).is_err());
+}
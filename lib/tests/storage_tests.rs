@@ -0,0 +1,127 @@
+use chrono::{NaiveDate, NaiveTime};
+use obsidian_logging::commands::add::add_entry_for_date;
+use obsidian_logging::config::{Config, ListType, TimeFormat};
+use obsidian_logging::document::DailyLog;
+use obsidian_logging::storage::{write_atomic, VaultLock};
+use obsidian_logging::Error;
+use std::fs::{self, read_to_string};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn setup_test_env() -> (TempDir, Config) {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        vault: temp_dir.path().to_str().unwrap().to_string(),
+        file_path_format: "{date}.md".to_string(),
+        section_header: "## Test".to_string(),
+        list_type: ListType::Bullet,
+        template_path: None,
+        locale: None,
+        time_format: TimeFormat::Hour24,
+        time_label: "Tidspunkt".to_string(),
+        event_label: "Hendelse".to_string(),
+        category_headers: std::collections::HashMap::new(),
+        phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
+    };
+    (temp_dir, config)
+}
+
+#[test]
+fn test_write_atomic_replaces_content_without_leftovers() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("note.md");
+
+    write_atomic(&path, "first\n").unwrap();
+    write_atomic(&path, "second\n").unwrap();
+
+    assert_eq!(read_to_string(&path).unwrap(), "second\n");
+    let names: Vec<String> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, vec!["note.md".to_string()]);
+}
+
+#[test]
+fn test_write_atomic_fails_for_missing_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("missing").join("note.md");
+    assert!(write_atomic(&path, "content").is_err());
+}
+
+#[test]
+fn test_vault_lock_refuses_missing_vault() {
+    let (temp_dir, mut config) = setup_test_env();
+    let vault = temp_dir.path().join("mistyped");
+    config.vault = vault.to_str().unwrap().to_string();
+
+    assert!(matches!(
+        VaultLock::acquire(&config),
+        Err(Error::ConfigInvalid { .. })
+    ));
+    assert!(!vault.exists());
+}
+
+#[test]
+fn test_vault_lock_is_exclusive() {
+    let (_temp_dir, config) = setup_test_env();
+    let lock = VaultLock::acquire(&config).unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+    let thread_config = config.clone();
+    let handle = thread::spawn(move || {
+        let _lock = VaultLock::acquire(&thread_config).unwrap();
+        sender.send(()).unwrap();
+    });
+
+    // The second lock cannot be taken while the first is held
+    assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+    drop(lock);
+    receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_concurrent_adds_keep_all_entries() {
+    let (_temp_dir, config) = setup_test_env();
+    let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    let time = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let config = config.clone();
+            thread::spawn(move || {
+                add_entry_for_date(
+                    date,
+                    vec![format!("Entry {}", i)],
+                    Some(time),
+                    &config,
+                    true,
+                    None,
+                )
+                .unwrap();
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let path = std::path::Path::new(&config.vault).join("2024-01-15.md");
+    let log = DailyLog::load(&path, &config).unwrap().unwrap();
+    let mut texts: Vec<String> = log.entries().map(|e| e.text.clone()).collect();
+    texts.sort();
+    let expected: Vec<String> = (0..8).map(|i| format!("Entry {}", i)).collect();
+    assert_eq!(texts, expected);
+}
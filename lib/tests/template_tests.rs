@@ -1,7 +1,11 @@
-use chrono::{Duration, Local};
+use chrono::{Duration, Local, NaiveDate};
 use obsidian_logging::config::{Config, ListType, TimeFormat};
-use obsidian_logging::template::{get_template_content, process_template, TemplateData};
+use obsidian_logging::template::{
+    get_template_content, process_template, render_template, TemplateData,
+};
+use obsidian_logging::Warning;
 use regex::Regex;
+use std::collections::HashMap;
 use std::fs;
 use tempfile::TempDir;
 
@@ -66,7 +70,7 @@ Created: {{created}}"#;
     fs::write(&template_path, template_content).unwrap();
 
     let data = TemplateData::new(None);
-    let result = process_template(template_path.to_str().unwrap(), &data);
+    let (result, _) = process_template(template_path.to_str().unwrap(), &data).unwrap();
 
     assert!(result.contains(&data.today));
     assert!(result.contains(&data.weekday));
@@ -75,6 +79,24 @@ Created: {{created}}"#;
     assert!(result.contains(&data.created));
 }
 
+#[test]
+fn test_process_template_returns_warnings() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let template_path = temp_dir.path().join("template.md");
+    fs::write(&template_path, "# {{title}}\n").unwrap();
+
+    let (result, warnings) =
+        process_template(template_path.to_str().unwrap(), &TemplateData::new(None)).unwrap();
+    assert_eq!(result, "# {{title}}\n");
+    assert_eq!(
+        warnings,
+        vec![Warning::new(
+            &template_path,
+            "unknown placeholder '{title}'"
+        )]
+    );
+}
+
 #[test]
 fn test_get_template_content_with_template() {
     let temp_dir = TempDir::new().unwrap();
@@ -90,9 +112,18 @@ fn test_get_template_content_with_template() {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
-    let content = get_template_content(&config);
+    let (content, _) = get_template_content(&config).unwrap();
     assert_eq!(content, "## 🕗\n\n");
 }
 
@@ -111,8 +142,116 @@ fn test_get_template_content_no_template() {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     };
 
-    let content = get_template_content(&config);
+    let (content, _) = get_template_content(&config).unwrap();
     assert_eq!(content, "## 🕗\n\n");
 }
+
+fn data_for(date: &str, locale: Option<&str>) -> TemplateData {
+    TemplateData::for_date(date.parse::<NaiveDate>().unwrap(), locale)
+}
+
+#[test]
+fn test_render_date_expressions() {
+    // 2024-01-31 is a Wednesday in ISO week 5
+    let data = data_for("2024-01-31", None);
+    let (result, warnings) = render_template(
+        "{date+7d:%Y-%m-%d} {date-1w} {date+1m} {today+1y:%Y} {week} {quarter} {{month_name}}",
+        &data,
+    );
+    assert_eq!(result, "2024-02-07 2024-01-24 2024-02-29 2025 05 1 January");
+    assert!(warnings.is_empty());
+
+    let data = data_for("2024-05-17", Some("nb_NO"));
+    let (result, _) = render_template("{date:%A %d. %B} {tomorrow:%A}", &data);
+    assert_eq!(result, "fredag 17. mai lørdag");
+
+    // Locale tags fall back to the language, and then to English
+    let data = data_for("2024-05-17", Some("sv-FI"));
+    let (result, _) = render_template("{weekday} {long_date} {date+1d:short}", &data);
+    assert_eq!(result, "fredag fredag 17 maj 2024 18.05.2024");
+    let data = data_for("2024-05-17", Some("en_GB"));
+    let (result, _) = render_template("{weekday} {long_date}", &data);
+    assert_eq!(result, "friday Friday 17 May 2024");
+}
+
+#[test]
+fn test_render_variables() {
+    std::env::set_var("OBSIDIAN_LOGGING_TEMPLATE_TEST", "from env");
+    let mut variables = HashMap::new();
+    variables.insert("team".to_string(), "Platform".to_string());
+    let data = data_for("2024-01-31", None).with_variables(variables);
+
+    let (result, warnings) = render_template(
+        "{team} {env:OBSIDIAN_LOGGING_TEMPLATE_TEST} {missing} {date:%Q}",
+        &data,
+    );
+    assert_eq!(result, "Platform from env {missing} {date:%Q}");
+    assert_eq!(
+        warnings,
+        vec![
+            "unknown placeholder '{missing}'".to_string(),
+            "invalid date format '%Q'".to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_render_keeps_obsidian_placeholders() {
+    let (result, warnings) = render_template(
+        "# {{title}}\n\n{{time:HH:mm}} {{title}} on {{date}}\n",
+        &data_for("2024-01-31", None),
+    );
+    assert_eq!(
+        result,
+        "# {{title}}\n\n{{time:HH:mm}} {{title}} on 2024-01-31\n"
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            "unknown placeholder '{title}'".to_string(),
+            "unknown placeholder '{time:HH:mm}'".to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_render_conditionals() {
+    let template = "# {today}\n{if weekend}\nRest\n{else}\n## Standup\n{end}\n## Log\n";
+
+    let (result, warnings) = render_template(template, &data_for("2024-06-01", None));
+    assert_eq!(result, "# 2024-06-01\nRest\n## Log\n");
+    assert!(warnings.is_empty());
+
+    let (result, _) = render_template(template, &data_for("2024-06-03", None));
+    assert_eq!(result, "# 2024-06-03\n## Standup\n## Log\n");
+
+    let (result, _) = render_template(
+        "{if monday}Plan{end}{if not friday} the week{end}",
+        &data_for("2024-06-03", None),
+    );
+    assert_eq!(result, "Plan the week");
+
+    let (result, warnings) = render_template(
+        "{if rainy}x{end}{if weekend}y",
+        &data_for("2024-06-01", None),
+    );
+    assert_eq!(result, "y");
+    assert_eq!(
+        warnings,
+        vec![
+            "{if weekend} without {end}".to_string(),
+            "unknown condition '{if rainy}'".to_string()
+        ]
+    );
+}
@@ -1,8 +1,10 @@
 use chrono::{NaiveDate, NaiveTime};
-use obsidian_logging::config::{Config, ListType, TimeFormat};
+use obsidian_logging::config::{Config, ListType, Period, TimeFormat};
 use obsidian_logging::utils::{
-    extract_log_entries, format_time, get_log_path_for_date, parse_time,
+    extract_log_entries, format_time, get_log_path_for_date, normalize_entry_text,
+    parse_date_expression, parse_log_path, parse_time, validate_file_path_format,
 };
+use obsidian_logging::Error;
 use std::path::PathBuf;
 
 fn create_test_config() -> Config {
@@ -18,6 +20,15 @@ fn create_test_config() -> Config {
         event_label: "Hendelse".to_string(),
         category_headers: std::collections::HashMap::new(),
         phrases: std::collections::HashMap::new(),
+        profile: None,
+        periodic_notes: std::collections::HashMap::new(),
+        template_variables: std::collections::HashMap::new(),
+        front_matter: Default::default(),
+        translations_dir: None,
+        entry_format: None,
+        table_columns: Vec::new(),
+        section_patterns: std::collections::HashMap::new(),
+        section_end: None,
     }
 }
 
@@ -26,7 +37,7 @@ fn test_get_log_path_for_date() {
     let config = create_test_config();
     let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
 
-    let path = get_log_path_for_date(date, &config);
+    let path = get_log_path_for_date(date, &config).unwrap();
     let mut expected_path = PathBuf::from("/test/vault");
     expected_path.push("test");
     expected_path.push("2024");
@@ -188,9 +199,10 @@ fn test_extract_log_entries_convert_table_to_bullet() {
         false,
     );
 
-    // Should convert to bullet format (with seconds added during reformatting)
-    assert_eq!(entries[0], "- 09:00:00 First entry");
-    assert_eq!(entries[1], "- 10:30:00 Second entry");
+    // Should convert to bullet format (with seconds added during reformatting),
+    // using the same bullet marker as newly added entries
+    assert_eq!(entries[0], "* 09:00:00 First entry");
+    assert_eq!(entries[1], "* 10:30:00 Second entry");
 }
 
 #[test]
@@ -346,3 +358,193 @@ Some content
         ]
     );
 }
+
+#[test]
+fn test_parse_date_expression() {
+    let today = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+    assert_eq!(
+        parse_date_expression("2024-01-02", today),
+        NaiveDate::from_ymd_opt(2024, 1, 2)
+    );
+    assert_eq!(parse_date_expression("today", today), Some(today));
+    assert_eq!(
+        parse_date_expression("Yesterday", today),
+        NaiveDate::from_ymd_opt(2024, 3, 14)
+    );
+    assert_eq!(
+        parse_date_expression("tomorrow", today),
+        NaiveDate::from_ymd_opt(2024, 3, 16)
+    );
+    assert_eq!(
+        parse_date_expression("7d", today),
+        NaiveDate::from_ymd_opt(2024, 3, 8)
+    );
+    assert_eq!(
+        parse_date_expression("2w", today),
+        NaiveDate::from_ymd_opt(2024, 3, 1)
+    );
+
+    assert_eq!(parse_date_expression("soon", today), None);
+    assert_eq!(parse_date_expression("2024-13-01", today), None);
+    assert_eq!(parse_date_expression("xd", today), None);
+}
+
+#[test]
+fn test_parse_log_path() {
+    let config = create_test_config();
+
+    assert_eq!(
+        parse_log_path("test/2024/03/2024-03-15.md", &config),
+        NaiveDate::from_ymd_opt(2024, 3, 15)
+    );
+    // Tokens must agree with each other
+    assert_eq!(parse_log_path("test/2024/04/2024-03-15.md", &config), None);
+    assert_eq!(parse_log_path("test/2024/03/notes.md", &config), None);
+    assert_eq!(parse_log_path("other/2024/03/2024-03-15.md", &config), None);
+}
+
+#[test]
+fn test_normalize_entry_text() {
+    assert_eq!(normalize_entry_text("  single line  \n"), "single line");
+    assert_eq!(
+        normalize_entry_text("\n\nfirst  \n    indented\n\nlast\n\n"),
+        "first\n    indented\n\nlast"
+    );
+    assert_eq!(
+        normalize_entry_text("\r\nwindows\r\nlines\r\n"),
+        "windows\nlines"
+    );
+    assert_eq!(normalize_entry_text(" \n\t\n"), "");
+}
+
+#[test]
+fn test_path_tokens() {
+    let mut config = create_test_config();
+    let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+    let relative = |config: &Config| {
+        get_log_path_for_date(date, config)
+            .unwrap()
+            .strip_prefix("/test/vault")
+            .unwrap()
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    config.file_path_format = "Journal/{year}/W{week}/{date} {weekday}.md".to_string();
+    assert_eq!(relative(&config), "Journal/2026/W42/2026-10-16 Friday.md");
+
+    config.file_path_format =
+        "{week_year}/Q{quarter}/{month_name}/{day} ({day_of_year}).md".to_string();
+    assert_eq!(relative(&config), "2026/Q4/October/16 (289).md");
+
+    config.locale = Some("nb_NO".to_string());
+    config.file_path_format = "{date:%Y/%m-%B}/{weekday} {day}.md".to_string();
+    assert_eq!(relative(&config), "2026/10-oktober/fredag 16.md");
+
+    config.locale = Some("de".to_string());
+    config.file_path_format = "{year}/{short_month_name}/{long_date}.md".to_string();
+    assert_eq!(relative(&config), "2026/Okt/Freitag 16. Oktober 2026.md");
+    assert_eq!(
+        parse_log_path("2026/Okt/Freitag 16. Oktober 2026.md", &config),
+        Some(date)
+    );
+
+    // The short date of the locale would be 10/16/2026, one directory per part
+    config.locale = Some("en_US".to_string());
+    config.file_path_format = "{short_date}.md".to_string();
+    assert_eq!(relative(&config), "2026-10-16.md");
+    assert_eq!(parse_log_path("2026-10-16.md", &config), Some(date));
+}
+
+#[test]
+fn test_parse_log_path_with_tokens() {
+    let mut config = create_test_config();
+
+    config.file_path_format = "Journal/{year}/W{week}/{date} {weekday}.md".to_string();
+    assert_eq!(
+        parse_log_path("Journal/2026/W42/2026-10-16 Friday.md", &config),
+        NaiveDate::from_ymd_opt(2026, 10, 16)
+    );
+    assert_eq!(
+        parse_log_path("Journal/2026/W42/2026-10-16 Monday.md", &config),
+        None
+    );
+
+    // The date can come from the ISO week and the weekday alone
+    config.locale = Some("de_DE".to_string());
+    config.file_path_format = "{week_year}/W{week}/{weekday}.md".to_string();
+    assert_eq!(parse_log_path("2027/W53/Sonntag.md", &config), None);
+    assert_eq!(
+        parse_log_path("2026/W53/Sonntag.md", &config),
+        NaiveDate::from_ymd_opt(2027, 1, 3)
+    );
+
+    config.file_path_format = "{date:%Y/%b/%e}.md".to_string();
+    assert_eq!(
+        parse_log_path("2024/Mär/ 5.md", &config),
+        NaiveDate::from_ymd_opt(2024, 3, 5)
+    );
+
+    config.file_path_format = "{year}/{day_of_year}.md".to_string();
+    assert_eq!(
+        parse_log_path("2024/060.md", &config),
+        NaiveDate::from_ymd_opt(2024, 2, 29)
+    );
+}
+
+#[test]
+fn test_validate_file_path_format() {
+    let mut config = create_test_config();
+    assert!(validate_file_path_format(&config, Period::Daily).is_ok());
+
+    config.file_path_format = "Journal/{week_year}/W{week}/{weekday}.md".to_string();
+    assert!(validate_file_path_format(&config, Period::Daily).is_ok());
+
+    config.file_path_format = "Journal/{year}/{month}.md".to_string();
+    assert_eq!(
+        validate_file_path_format(&config, Period::Daily),
+        Err(
+            "file_path_format 'Journal/{year}/{month}.md' does not identify the day of the note"
+                .to_string()
+        )
+    );
+
+    config.file_path_format = "{date:%Q}.md".to_string();
+    assert!(validate_file_path_format(&config, Period::Daily)
+        .unwrap_err()
+        .contains("invalid date format"));
+}
+
+#[test]
+fn test_get_log_path_for_date_refuses_invalid_date_format() {
+    let mut config = create_test_config();
+    config.file_path_format = "{date:%Q}.md".to_string();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+    match get_log_path_for_date(date, &config) {
+        Err(Error::ConfigInvalid { problems, .. }) => {
+            assert_eq!(
+                problems,
+                vec!["file_path_format '{date:%Q}.md' contains an invalid date format"]
+            );
+        }
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_extract_log_entries_keeps_blank_lines_after_the_section() {
+    let content = "## Test\n* 09:00 First entry\n\n## Notes\n\nSome notes\n\n## Links\n";
+    let config = create_test_config();
+    let (_, after, entries, _) = extract_log_entries(
+        content,
+        &config.section_header,
+        &ListType::Bullet,
+        &config,
+        false,
+    );
+
+    assert_eq!(entries, vec!["* 09:00 First entry"]);
+    assert_eq!(after, "## Notes\n\nSome notes\n\n## Links\n");
+}